@@ -7,13 +7,18 @@ use std::fs;
 
 use chrono::prelude::*;
 use clap::Parser;
-use simplelog::{Config, LevelFilter, WriteLogger};
+use simplelog::{LevelFilter, WriteLogger};
 
 use terminal::launch;
+use viewer::Config;
 
 #[derive(Parser, Default, Debug)]
 struct Cli {
     path: String,
+
+    /// Path to the config file, `~/.config/dot-viewer/config.toml` on default
+    #[arg(short, long)]
+    config: Option<String>,
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -21,9 +26,11 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     fs::create_dir_all("./logs")?;
     let file = fs::File::create(format!("logs/log_{}.log", Local::now()))?;
-    WriteLogger::init(LevelFilter::Info, Config::default(), file)?;
+    WriteLogger::init(LevelFilter::Info, simplelog::Config::default(), file)?;
+
+    let config = Config::load(args.config.as_deref())?;
 
-    launch(args.path)?;
+    launch(args.path, config)?;
 
     Ok(())
 }
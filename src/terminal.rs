@@ -1,11 +1,20 @@
-use crate::{ui, viewer::App};
+use crate::{
+    ui,
+    viewer::{App, Config, DotViewerError, Job},
+};
 
-use std::io::Stdout;
-use std::{error::Error, io};
+use std::io::{Stdout, Write};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+use std::{error::Error, io, thread};
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     execute,
+    style::Print,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use log::error;
@@ -14,17 +23,36 @@ use tui::{
     Terminal,
 };
 
-pub fn launch(path: String) -> Result<(), Box<dyn Error>> {
+/// Interval between ticks while idle, on which spinners turn and background jobs are polled
+const TICK: Duration = Duration::from_millis(100);
+
+/// `Message` wakes up the event loop, with either an input event or a tick.
+enum Message {
+    Input(Event),
+    Tick,
+}
+
+pub fn launch(path: String, config: Config) -> Result<(), Box<dyn Error>> {
     // setup terminal
     let mut terminal = setup()?;
+    let messages = listen();
 
-    // create and run app
-    let app = App::new(&path).map_err(|_| {
-        let _ = cleanup();
+    // create app on a background job, parsing the dot file while showing its progress
+    let job = Job::spawn(format!("parsing {path}"), Box::new(move |_| App::new(&path, config)));
+    let app = match load(&mut terminal, &messages, job)? {
+        Some(app) => app.map_err(|e| {
+            let _ = cleanup();
+
+            match e {
+                DotViewerError::ConfigError(_) => Box::<dyn Error>::from(e.to_string()),
+                _ => Box::<dyn Error>::from("user should provide path to a valid dot file"),
+            }
+        })?,
+        None => return cleanup(),
+    };
 
-        Box::<dyn Error>::from("user should provide path to a valid dot file")
-    })?;
-    let _ = run(&mut terminal, app);
+    // run app
+    let _ = run(&mut terminal, &messages, app);
 
     // restore terminal
     cleanup()?;
@@ -36,6 +64,11 @@ fn setup() -> Result<Terminal<CrosstermBackend<Stdout>>, Box<dyn Error>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+
+    // tell apart keys sent alike, e.g., `ctrl-i` from `tab`, in terminals supporting the kitty
+    // keyboard protocol, while the others ignore the sequence
+    let flags = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES;
+    let _ = execute!(stdout, PushKeyboardEnhancementFlags(flags));
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
 
@@ -56,12 +89,87 @@ fn setup_panic_hook() {
     }));
 }
 
-fn run<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
+/// Listen to input events on a thread, sending ticks in between while idle.
+fn listen() -> Receiver<Message> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || loop {
+        let message = match event::poll(TICK) {
+            Ok(true) => match event::read() {
+                Ok(event) => Message::Input(event),
+                Err(_) => break,
+            },
+            Ok(false) => Message::Tick,
+            Err(_) => break,
+        };
+
+        // the event loop is gone on quit
+        if sender.send(message).is_err() {
+            break;
+        }
+    });
+
+    receiver
+}
+
+/// Wait for the `job` creating app, showing its status, unless quit by `esc`.
+/// Parsing can not be interrupted, so quitting leaves it to end with the process.
+fn load<B: Backend>(
+    terminal: &mut Terminal<B>,
+    messages: &Receiver<Message>,
+    job: Job<App>,
+) -> io::Result<Option<Result<App, DotViewerError>>> {
     loop {
-        terminal.draw(|f| ui::draw_app(f, &mut app))?;
+        if let Some(app) = job.poll() {
+            return Ok(Some(app));
+        }
+
+        terminal.draw(|f| ui::draw_loading(f, &job.status()))?;
+
+        match messages.recv() {
+            Ok(Message::Input(Event::Key(key))) if key.code == KeyCode::Esc => return Ok(None),
+            Ok(_) => {}
+            Err(_) => return Ok(None),
+        }
+    }
+}
+
+fn run<B: Backend + Write>(
+    terminal: &mut Terminal<B>,
+    messages: &Receiver<Message>,
+    mut app: App,
+) -> io::Result<()> {
+    let mut redraw = true;
+
+    loop {
+        if redraw {
+            terminal.draw(|f| ui::draw_app(f, &mut app))?;
+        }
 
-        if let Event::Key(key) = event::read()? {
-            app.key(key);
+        let message = match messages.recv() {
+            Ok(message) => message,
+            Err(_) => break,
+        };
+
+        // ticks redraw only while background jobs are running, to turn their spinners
+        redraw = match message {
+            Message::Input(Event::Key(key)) => {
+                app.key(key);
+                true
+            }
+            Message::Input(Event::Mouse(mouse)) => {
+                app.mouse(mouse);
+                true
+            }
+            Message::Input(_) => true,
+            Message::Tick => app.busy(),
+        };
+
+        app.tick();
+
+        // yanked texts are copied by OSC 52 sequences, which are not drawn but read by the terminal
+        if let Some(osc52) = app.clipboard.pending.take() {
+            execute!(terminal.backend_mut(), Print(osc52))?;
         }
 
         if app.quit {
@@ -74,6 +182,7 @@ fn run<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
 
 fn cleanup() -> Result<(), Box<dyn Error>> {
     let mut stdout = io::stdout();
+    let _ = execute!(stdout, PopKeyboardEnhancementFlags);
     execute!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
     disable_raw_mode()?;
 
@@ -1,23 +1,17 @@
-use crate::ui::{input::draw_input, popup::draw_popup, tabs::draw_tabs};
+use crate::ui::{input::draw_input, popup::draw_popup, status::draw_status, tabs::draw_tabs};
 use crate::viewer::{App, Mode};
 
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    widgets::{Block, BorderType, Borders},
+    widgets::{Block, BorderType, Borders, Paragraph},
     Frame,
 };
 
 pub(crate) fn draw_app<B: Backend>(f: &mut Frame<B>, app: &mut App) {
     let size = f.size();
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .title("Dot-Viewer (v0.1.0)")
-        .title_alignment(Alignment::Center)
-        .border_type(BorderType::Rounded);
-
-    f.render_widget(block, size);
+    f.render_widget(frame(), size);
 
     match &app.mode {
         Mode::Normal | Mode::Command | Mode::Search(_) => draw_main(f, size, app),
@@ -28,9 +22,39 @@ pub(crate) fn draw_app<B: Backend>(f: &mut Frame<B>, app: &mut App) {
 fn draw_main<B: Backend>(f: &mut Frame<B>, size: Rect, app: &mut App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(90), Constraint::Percentage(10)].as_ref())
+        .constraints([Constraint::Min(0), Constraint::Length(4)].as_ref())
         .split(size);
 
-    draw_tabs(f, chunks[0], app);
+    // the status line takes the bottom margin of the tabs
+    let tabs = chunks[0];
+    let status = Rect {
+        x: tabs.x + 1,
+        y: tabs.bottom().saturating_sub(1),
+        width: tabs.width.saturating_sub(2),
+        height: tabs.height.min(1),
+    };
+
+    draw_tabs(f, tabs, app);
+    draw_status(f, status, app);
     draw_input(f, chunks[1], app);
 }
+
+/// Draw the loading screen, showing the `status` of the job loading the dot file.
+pub(crate) fn draw_loading<B: Backend>(f: &mut Frame<B>, status: &str) {
+    let size = f.size();
+
+    f.render_widget(frame(), size);
+
+    let text = format!("{status}\n\nesc to quit");
+    let area =
+        Rect { y: size.y + size.height.saturating_sub(3) / 2, height: size.height.min(3), ..size };
+    f.render_widget(Paragraph::new(text).alignment(Alignment::Center), area);
+}
+
+fn frame() -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .title("Dot-Viewer (v0.1.0)")
+        .title_alignment(Alignment::Center)
+        .border_type(BorderType::Rounded)
+}
@@ -4,7 +4,7 @@ use crate::viewer::{App, Mode, SearchMode};
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::Style,
     widgets::Paragraph,
     Frame,
 };
@@ -23,6 +23,7 @@ pub(super) fn draw_input<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut Ap
     let block = surrounding_block(
         title.to_string(),
         matches!(app.mode, Mode::Command) || matches!(app.mode, Mode::Search(_)),
+        &app.theme,
     );
 
     f.render_widget(block, chunk);
@@ -38,14 +39,13 @@ pub(super) fn draw_input<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut Ap
 }
 
 fn draw_result<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
-    let (msg, color) = match &app.result {
-        Ok(succ) => (succ.to_string(), Color::Green),
-        Err(err) => (err.to_string(), Color::Red),
+    let (msg, style) = match &app.result {
+        Ok(succ) => (succ.to_string(), app.theme.success),
+        Err(err) => (err.to_string(), app.theme.error),
     };
 
     if !msg.is_empty() {
-        let msg =
-            Paragraph::new(msg).style(Style::default().fg(color).add_modifier(Modifier::BOLD));
+        let msg = Paragraph::new(msg).style(style);
         f.render_widget(msg, chunk);
     }
 }
@@ -53,7 +53,7 @@ fn draw_result<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
 fn draw_form<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
     let input = Paragraph::new(app.input.key.clone()).style(match &app.mode {
         Mode::Normal => Style::default(),
-        Mode::Command | Mode::Search(_) => Style::default().fg(Color::Yellow),
+        Mode::Command | Mode::Search(_) => app.theme.input,
         _ => unreachable!(),
     });
     f.render_widget(input, chunk);
@@ -1,22 +1,23 @@
 mod app;
 mod input;
 mod popup;
+mod status;
 mod tabs;
-mod utils;
 mod view;
 
+use crate::viewer::Theme;
+
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
     widgets::{Block, Borders},
 };
 
-pub(crate) use crate::ui::app::draw_app;
+pub(crate) use crate::ui::app::{draw_app, draw_loading};
 
-pub(super) fn surrounding_block(title: String, highlight: bool) -> Block<'static> {
-    let color = if highlight { Color::Yellow } else { Color::White };
+pub(super) fn surrounding_block(title: String, highlight: bool, theme: &Theme) -> Block<'static> {
+    let style = if highlight { theme.border_focused } else { theme.border };
 
-    Block::default().borders(Borders::ALL).border_style(Style::default().fg(color)).title(title)
+    Block::default().borders(Borders::ALL).border_style(style).title(title)
 }
 
 pub(super) fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
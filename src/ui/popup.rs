@@ -4,8 +4,9 @@ use crate::viewer::{App, Mode, PopupMode};
 use tui::{
     backend::Backend,
     layout::{Constraint, Rect},
-    style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, Row, Table},
+    style::Style,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table},
     Frame,
 };
 use tui_tree_widget::Tree as TUITree;
@@ -15,7 +16,12 @@ pub(super) fn draw_popup<B: Backend>(f: &mut Frame<B>, size: Rect, app: &mut App
 
     match &app.mode {
         Mode::Popup(pmode) => match pmode {
-            PopupMode::Tree => draw_tree(f, popup, app),
+            PopupMode::Tree | PopupMode::TreeFilter => draw_tree(f, popup, app),
+            PopupMode::Marks => draw_marks(f, popup, app),
+            PopupMode::Lineage => draw_lineage(f, popup, app),
+            PopupMode::Legend => draw_legend(f, popup, app),
+            PopupMode::Messages => draw_messages(f, popup, app),
+            PopupMode::Sketch => draw_sketch(f, popup, app),
             PopupMode::Help => draw_help(f, popup, app),
         },
         _ => unreachable!(),
@@ -23,16 +29,27 @@ pub(super) fn draw_popup<B: Backend>(f: &mut Frame<B>, size: Rect, app: &mut App
 }
 
 fn draw_tree<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
-    let block = surrounding_block("Select a subgraph".to_string(), false);
-
     let view = app.tabs.selected();
     let subtree = &mut view.subtree;
 
+    let sort = subtree.sort.name();
+    let title = match &app.mode {
+        Mode::Popup(PopupMode::TreeFilter) => {
+            format!("Select a subgraph (by {sort}) /{}_", subtree.filter)
+        }
+        _ if !subtree.filter.is_empty() => {
+            format!("Select a subgraph (by {sort}) /{}", subtree.filter)
+        }
+        _ => format!("Select a subgraph (by {sort})"),
+    };
+    let block = surrounding_block(title, false, &app.theme);
+
+    let inner = Block::default().borders(Borders::ALL);
+    subtree.frame(inner.inner(chunk));
+
     let tree = TUITree::new(subtree.tree.clone())
-        .block(Block::default().borders(Borders::ALL))
-        .highlight_style(
-            Style::default().fg(Color::Black).bg(Color::LightGreen).add_modifier(Modifier::BOLD),
-        )
+        .block(inner)
+        .highlight_style(app.theme.popup_selected)
         .highlight_symbol(">> ");
 
     f.render_stateful_widget(tree, chunk, &mut subtree.state);
@@ -40,10 +57,157 @@ fn draw_tree<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
     f.render_widget(block, chunk);
 }
 
+fn draw_marks<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
+    let block = surrounding_block("Marks".to_string(), false, &app.theme);
+    app.marks.list.frame(block.inner(chunk));
+
+    let list: Vec<ListItem> = (app.marks.list.items.iter())
+        .map(|(name, id)| {
+            let name = Span::styled(format!("{name}  "), app.theme.accent);
+            ListItem::new(Spans::from(vec![name, Span::raw(id.as_str())]))
+        })
+        .collect();
+
+    let list =
+        List::new(list).block(block).highlight_style(app.theme.selected).highlight_symbol("> ");
+
+    f.render_stateful_widget(list, chunk, &mut app.marks.list.state);
+}
+
+fn draw_lineage<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
+    let block = surrounding_block("Tabs".to_string(), false, &app.theme);
+    app.lineage.list.frame(block.inner(chunk));
+
+    let list: Vec<ListItem> = (app.lineage.list.items.iter())
+        .map(|&(idx, depth)| {
+            let view = &app.tabs.tabs[idx];
+
+            let indent =
+                if depth == 0 { String::new() } else { format!("{}└ ", "  ".repeat(depth - 1)) };
+            let style = if idx == app.tabs.state { app.theme.accent } else { Style::default() };
+            let mut spans = vec![
+                Span::raw(format!("{}  {indent}", idx + 1)),
+                Span::styled(view.title.as_str(), style),
+            ];
+            if let Some(origin) = &view.origin {
+                spans.push(Span::styled(format!("  ({})", origin.derivation), app.theme.hint));
+            }
+
+            ListItem::new(Spans::from(spans))
+        })
+        .collect();
+
+    let list =
+        List::new(list).block(block).highlight_style(app.theme.selected).highlight_symbol("> ");
+
+    f.render_stateful_widget(list, chunk, &mut app.lineage.list.state);
+}
+
+fn draw_legend<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
+    let title = format!("Colors in {}", app.tabs.selected().short_title());
+    let block = surrounding_block(title, false, &app.theme);
+    app.legend.frame(block.inner(chunk));
+
+    let list: Vec<ListItem> = (app.legend.items.iter())
+        .map(|(paint, count)| {
+            let swatch = if app.theme.colored { paint.style() } else { Style::default() };
+            let kind = if paint.filled { "fill" } else { "color" };
+            let nodes = if *count == 1 { "node" } else { "nodes" };
+
+            ListItem::new(Spans::from(vec![
+                Span::styled(format!(" {} ", paint.spec), swatch),
+                Span::styled(format!("  {kind}"), app.theme.hint),
+                Span::raw(format!("  {count} {nodes}")),
+            ]))
+        })
+        .collect();
+
+    let list =
+        List::new(list).block(block).highlight_style(app.theme.selected).highlight_symbol("> ");
+
+    f.render_stateful_widget(list, chunk, &mut app.legend.state);
+}
+
+fn draw_messages<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
+    let block = surrounding_block("Messages".to_string(), false, &app.theme);
+    app.messages.list.frame(block.inner(chunk));
+
+    let list: Vec<ListItem> = (app.messages.list.items.iter())
+        .map(|message| {
+            let style = if message.error { app.theme.error } else { app.theme.success };
+            let mut spans = vec![
+                Span::styled(format!("{:>4}  ", message.age()), app.theme.hint),
+                Span::styled(message.text.clone(), style),
+            ];
+            if message.count > 1 {
+                spans.push(Span::styled(format!("  (x{})", message.count), app.theme.hint));
+            }
+
+            ListItem::new(Spans::from(spans))
+        })
+        .collect();
+
+    let list =
+        List::new(list).block(block).highlight_style(app.theme.selected).highlight_symbol("> ");
+
+    f.render_stateful_widget(list, chunk, &mut app.messages.list.state);
+}
+
+fn draw_sketch<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
+    let sketch = match app.sketch.as_mut() {
+        Some(sketch) => sketch,
+        None => return,
+    };
+
+    let title = format!("Neighborhood of {} (depth {})", sketch.center, sketch.depth);
+    let block = surrounding_block(title, false, &app.theme);
+
+    let inner = block.inner(chunk);
+    let (height, width) = (inner.height as usize, inner.width as usize);
+    sketch.frame(inner);
+
+    // the selected node is highlighted over the center node
+    let boxes = [
+        (sketch.center_placement(), app.theme.accent),
+        (sketch.selected_placement(), app.theme.popup_selected),
+    ];
+
+    let (row, col) = sketch.offset;
+    let lines: Vec<Spans> = (sketch.lines.iter().enumerate().skip(row).take(height))
+        .map(|(y, line)| {
+            let mut spans = Vec::new();
+            let mut text = String::new();
+            let mut style = Style::default();
+
+            for (x, c) in line.chars().enumerate().skip(col).take(width) {
+                let next = (boxes.iter().rev())
+                    .find(|(placement, _)| placement.contains(x, y))
+                    .map_or(Style::default(), |(_, style)| *style);
+
+                if next != style && !text.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut text), style));
+                }
+                style = next;
+                text.push(c);
+            }
+            if !text.is_empty() {
+                spans.push(Span::styled(text, style));
+            }
+
+            Spans::from(spans)
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines).block(block), chunk);
+}
+
 fn draw_help<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
-    let header = app.help.header.iter().map(|s| {
-        Cell::from(s.as_str()).style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
-    });
+    // rows are rendered below the header, which takes two lines with its bottom margin
+    let block = Block::default().borders(Borders::ALL);
+    let inner = block.inner(chunk);
+    app.help.frame(Rect { y: inner.y + 2, height: inner.height.saturating_sub(2), ..inner });
+
+    let header = app.help.header.iter().map(|s| Cell::from(s.as_str()).style(app.theme.header));
     let header = Row::new(header).height(1).bottom_margin(1);
 
     let rows = (app.help.rows.iter()).map(|row| {
@@ -53,8 +217,8 @@ fn draw_help<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
 
     let table = Table::new(rows)
         .header(header)
-        .block(Block::default().borders(Borders::ALL))
-        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .block(block)
+        .highlight_style(app.theme.row_selected)
         .highlight_symbol("> ")
         .widths(&[
             Constraint::Percentage(15),
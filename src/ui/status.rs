@@ -0,0 +1,82 @@
+use crate::viewer::{App, Mode, SearchMode};
+
+use std::path::Path;
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// Draw the status line, showing the file, the current tab and its size, the mode,
+/// the pending keys, the active search, the running jobs and the number of errors logged.
+pub(super) fn draw_status<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
+    let theme = &app.theme;
+    let separator = || Span::styled(" │ ", theme.hint);
+
+    let file = Path::new(&app.path)
+        .file_name()
+        .map_or(app.path.clone(), |name| name.to_string_lossy().to_string());
+    let tab = format!("tab {}/{}", app.tabs.state + 1, app.tabs.tabs.len());
+
+    let view = &app.tabs.tabs[app.tabs.state];
+    let size = format!("{} nodes {} edges", view.current.items.len(), view.edges);
+
+    let mut spans = vec![
+        Span::styled(format!(" {file}"), theme.accent),
+        separator(),
+        Span::raw(tab),
+        separator(),
+        Span::raw(size),
+        separator(),
+        Span::styled(mode(&app.mode), theme.tab),
+    ];
+
+    // pending keys, as typed so far, e.g., `5g` or `m`
+    let count = app.count.map(|count| count.to_string()).unwrap_or_default();
+    let keys: String = app.keys.iter().map(|chord| chord.to_string()).collect();
+    let pending = app.pending.map(|action| format!("{action} …")).unwrap_or_default();
+    let typed = format!("{count}{keys}{pending}");
+    if !typed.is_empty() {
+        spans.extend([separator(), Span::styled(typed, theme.input)]);
+    }
+
+    if !view.key.is_empty() {
+        let prefix = match view.smode {
+            SearchMode::Fuzzy => "/",
+            SearchMode::Regex => "r",
+        };
+        let search = format!("{prefix}{} ({} matches)", view.key, view.matches.items.len());
+        spans.extend([separator(), Span::raw(search)]);
+    }
+
+    // running jobs, with a spinner and their progress
+    let search = app.search.as_ref().map(|job| job.status());
+    let yank = app.yank.as_ref().map(|job| job.status());
+    let job = app.job.as_ref().map(|job| format!("{} · esc to cancel", job.status()));
+    for status in search.into_iter().chain(yank).chain(job) {
+        spans.extend([separator(), Span::styled(status, theme.accent)]);
+    }
+
+    let errors = app.messages.errors();
+    if errors > 0 {
+        let noun = if errors == 1 { "error" } else { "errors" };
+        spans.extend([separator(), Span::styled(format!("{errors} {noun}"), theme.error)]);
+    }
+
+    f.render_widget(Paragraph::new(Spans::from(spans)), chunk);
+}
+
+fn mode(mode: &Mode) -> String {
+    let name = match mode {
+        Mode::Normal => "Normal",
+        Mode::Command => "Command",
+        Mode::Search(SearchMode::Fuzzy) => "Fuzzy Search",
+        Mode::Search(SearchMode::Regex) => "Regex Search",
+        Mode::Popup(_) => "Popup",
+    };
+
+    name.to_uppercase()
+}
@@ -1,10 +1,9 @@
-use crate::ui::view::draw_view;
-use crate::viewer::App;
+use crate::ui::{surrounding_block, view::draw_view};
+use crate::viewer::{App, Panes, Theme, View};
 
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
     text::{Span, Spans},
     widgets::{Block, Borders, Tabs},
     Frame,
@@ -19,22 +18,65 @@ pub(super) fn draw_tabs<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App
 
     draw_nav_bar(f, chunks[0], app);
 
+    let (other, left, sync) = match &app.tabs.split {
+        Some(split) => (split.other, split.left, split.sync),
+        None => {
+            let view = app.tabs.selected();
+            draw_view(f, chunks[1], view, &app.panes, &app.theme);
+            return;
+        }
+    };
+
+    let halves = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(chunks[1]);
+    let (active, inactive) = if left { (halves[0], halves[1]) } else { (halves[1], halves[0]) };
+
+    let sync = if sync { " (synced)" } else { "" };
+
+    // the selected tab is drawn last, such that its lists are hit by mouse if shown in both
+    let view = &mut app.tabs.tabs[other];
+    let title = format!("{} {}{sync}", other + 1, view.short_title());
+    draw_pane(f, inactive, view, title, false, &app.panes, &app.theme);
+    if let Some(split) = &mut app.tabs.split {
+        split.area = inactive;
+    }
+
+    let state = app.tabs.state;
     let view = app.tabs.selected();
-    draw_view(f, chunks[1], view);
+    let title = format!("{} {}{sync}", state + 1, view.short_title());
+    draw_pane(f, active, view, title, true, &app.panes, &app.theme);
+}
+
+fn draw_pane<B: Backend>(
+    f: &mut Frame<B>,
+    chunk: Rect,
+    view: &mut View,
+    title: String,
+    active: bool,
+    panes: &Panes,
+    theme: &Theme,
+) {
+    let block = surrounding_block(title, active, theme);
+
+    draw_view(f, block.inner(chunk), view, panes, theme);
+    f.render_widget(block, chunk);
 }
 
 fn draw_nav_bar<B: Backend>(f: &mut Frame<B>, chunk: Rect, app: &mut App) {
     let block = Block::default().borders(Borders::ALL).title("Views");
+    app.tabs.area = block.inner(chunk);
 
-    let titles: Vec<String> = app.tabs.tabs.iter().map(|view| view.title.clone()).collect();
+    let titles: Vec<String> = app.tabs.tabs.iter().map(|view| view.short_title()).collect();
     let titles = (titles.iter())
-        .map(|title| Spans::from(vec![Span::styled(title, Style::default().fg(Color::Yellow))]))
+        .map(|title| Spans::from(vec![Span::styled(title, app.theme.tab)]))
         .collect();
 
     let tabs = Tabs::new(titles)
         .block(block)
         .select(app.tabs.state)
-        .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(Color::Black));
+        .highlight_style(app.theme.tab_selected);
 
     f.render_widget(tabs, chunk)
 }
@@ -1,6 +1,6 @@
 use crate::{
-    ui::{surrounding_block, utils::htmlparser},
-    viewer::{Focus, View},
+    ui::surrounding_block,
+    viewer::{htmlparser, label_lines, AttrsMode, Focus, Orientation, Panes, Theme, View},
 };
 
 use std::collections::{HashMap, HashSet};
@@ -12,50 +12,160 @@ use rayon::prelude::*;
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::Style,
     text::{Span, Spans},
-    widgets::{Block, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table, Wrap},
     Frame,
 };
 
-pub(super) fn draw_view<B: Backend>(f: &mut Frame<B>, chunk: Rect, view: &mut View) {
+/// Spacing between columns of HTML-like table labels
+const TABLE_SPACING: u16 = 2;
+/// Maximum width of label summaries of prev and next nodes
+const SUMMARY: usize = 24;
+/// Width of the highlight symbol of node lists
+const HIGHLIGHT: usize = 2;
+/// Minimum width of the nodes list to show the minimap beside
+const MINIMAP_MIN: u16 = 8;
+
+pub(super) fn draw_view<B: Backend>(
+    f: &mut Frame<B>,
+    chunk: Rect,
+    view: &mut View,
+    panes: &Panes,
+    theme: &Theme,
+) {
+    // panes that are not drawn should not be hit by mouse
+    view.prevs.frame(Rect::default());
+    view.nexts.frame(Rect::default());
+    view.attrs.area = Rect::default();
+    view.minimap = Rect::default();
+
+    if panes.maximized {
+        match view.focus {
+            Focus::Current => draw_left(f, chunk, view, panes, theme),
+            Focus::Prev => {
+                view.current.frame(Rect::default());
+                draw_prevs(f, chunk, view, panes, theme)
+            }
+            Focus::Next => {
+                view.current.frame(Rect::default());
+                draw_nexts(f, chunk, view, panes, theme)
+            }
+            Focus::Attrs => {
+                view.current.frame(Rect::default());
+                draw_metadata(f, chunk, view, panes, theme)
+            }
+        }
+        return;
+    }
+
+    if !panes.show_adjacent && !panes.show_attrs {
+        draw_left(f, chunk, view, panes, theme);
+        return;
+    }
+
+    let direction = match panes.orientation {
+        Orientation::Horizontal => Direction::Horizontal,
+        Orientation::Vertical => Direction::Vertical,
+    };
     let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)].as_ref())
+        .direction(direction)
+        .constraints(
+            [Constraint::Percentage(panes.nodes), Constraint::Percentage(100 - panes.nodes)]
+                .as_ref(),
+        )
         .split(chunk);
 
-    draw_left(f, chunks[0], view);
-    draw_right(f, chunks[1], view);
+    draw_left(f, chunks[0], view, panes, theme);
+    draw_right(f, chunks[1], view, panes, theme);
 }
 
-fn draw_left<B: Backend>(f: &mut Frame<B>, chunk: Rect, view: &mut View) {
+fn draw_left<B: Backend>(
+    f: &mut Frame<B>,
+    chunk: Rect,
+    view: &mut View,
+    panes: &Panes,
+    theme: &Theme,
+) {
     if view.matches.items.is_empty() {
-        draw_current(f, chunk, view);
+        draw_current(f, chunk, view, panes, theme);
     } else {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Percentage(99), Constraint::Percentage(1)].as_ref())
             .split(chunk);
 
-        draw_current(f, chunks[0], view);
+        draw_current(f, chunks[0], view, panes, theme);
         draw_match(f, chunks[1], view);
     }
 }
 
-fn draw_right<B: Backend>(f: &mut Frame<B>, chunk: Rect, view: &mut View) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-        .split(chunk);
-
-    draw_adjacent(f, chunks[0], view);
-    draw_metadata(f, chunks[1], view);
+fn draw_right<B: Backend>(
+    f: &mut Frame<B>,
+    chunk: Rect,
+    view: &mut View,
+    panes: &Panes,
+    theme: &Theme,
+) {
+    // the other panes are stacked across the direction they are placed beside the nodes list
+    let direction = match panes.orientation {
+        Orientation::Horizontal => Direction::Vertical,
+        Orientation::Vertical => Direction::Horizontal,
+    };
+
+    match (panes.show_adjacent, panes.show_attrs) {
+        (true, true) => {
+            let chunks = Layout::default()
+                .direction(direction)
+                .constraints(
+                    [
+                        Constraint::Percentage(panes.adjacent),
+                        Constraint::Percentage(100 - panes.adjacent),
+                    ]
+                    .as_ref(),
+                )
+                .split(chunk);
+
+            draw_adjacent(f, chunks[0], view, panes, theme);
+            draw_metadata(f, chunks[1], view, panes, theme);
+        }
+        (true, false) => draw_adjacent(f, chunk, view, panes, theme),
+        (false, true) => draw_metadata(f, chunk, view, panes, theme),
+        (false, false) => {}
+    }
 }
 
-fn draw_current<B: Backend>(f: &mut Frame<B>, chunk: Rect, view: &mut View) {
+fn draw_current<B: Backend>(
+    f: &mut Frame<B>,
+    chunk: Rect,
+    view: &mut View,
+    panes: &Panes,
+    theme: &Theme,
+) {
     let progress = view.progress_current();
-    let title = format!("Nodes {progress}");
-    let block = surrounding_block(title, view.focus == Focus::Current);
+    let breadcrumb = view.breadcrumb();
+    let title = if breadcrumb.is_empty() {
+        format!("Nodes {progress}")
+    } else {
+        format!("Nodes {progress} {breadcrumb}")
+    };
+    let focused = view.focus == Focus::Current;
+    let block = surrounding_block(title, focused, theme);
+
+    // the minimap takes the rightmost column, beside the items but not the tooltip
+    let mut inner = block.inner(chunk);
+    let minimap = panes.minimap && inner.width > MINIMAP_MIN;
+    if minimap {
+        inner.width -= 1;
+    }
+
+    let fit = Fit::new(inner, view.current.shift, panes.elided);
+    let tooltip = tooltip(view, view.current.selected(), focused, &fit);
+    let (area, tooltip_area) = split_tooltip(inner, &tooltip);
+    view.current.frame(area);
+    if minimap {
+        view.minimap = Rect { x: area.right(), width: 1, ..area };
+    }
 
     let froms: HashSet<&String> = HashSet::from_iter(&view.prevs.items);
     let tos: HashSet<&String> = HashSet::from_iter(&view.nexts.items);
@@ -67,32 +177,83 @@ fn draw_current<B: Backend>(f: &mut Frame<B>, chunk: Rect, view: &mut View) {
     let list: Vec<ListItem> = (view.current.items.par_iter())
         .enumerate()
         .map(|(idx, id)| {
-            let mut spans: Vec<Span> = id.chars().map(|c| Span::raw(c.to_string())).collect();
+            // highlights are applied before fitting, such that they move along with characters
+            let mut glyphs = glyphs(view.text(id), Style::default());
             if let Some(&highlight) = matches.get(&idx) {
                 for &idx in highlight {
-                    spans[idx].style =
-                        Style::default().bg(Color::Rgb(120, 120, 120)).add_modifier(Modifier::BOLD);
+                    if let Some((_, style)) = glyphs.get_mut(idx) {
+                        *style = theme.matched;
+                    }
                 }
             }
 
-            let mut item = ListItem::new(Spans(spans));
-
+            // prev and next nodes are styled over the colors of nodes
+            let mut style = match view.paints.get(id) {
+                Some(paint) if theme.colored => paint.style(),
+                _ => Style::default(),
+            };
             if froms.contains(&id) {
-                item = item.style(Style::default().fg(Color::Rgb(255, 150, 150)));
+                style = style.patch(theme.prev);
             } else if tos.contains(&id) {
-                item = item.style(Style::default().fg(Color::Rgb(150, 150, 255)));
+                style = style.patch(theme.next);
             }
 
-            item
+            ListItem::new(fit.apply(glyphs, Vec::new())).style(style)
         })
         .collect();
 
-    let list = List::new(list)
-        .block(block)
-        .highlight_style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
-        .highlight_symbol("> ");
+    let list = List::new(list).highlight_style(theme.selected).highlight_symbol("> ");
+    let markers = markers(view, theme);
+
+    f.render_widget(block, chunk);
+    f.render_stateful_widget(list, area, &mut view.current.state);
+    f.render_widget(Paragraph::new(markers), view.minimap);
+    draw_tooltip(f, tooltip_area, tooltip, theme);
+}
+
+/// Rows of the minimap, marking the positions of the current node, prev and next nodes and matches
+/// in the nodes list at scale, over a scrollbar of the visible range of the list.
+fn markers(view: &View, theme: &Theme) -> Vec<Spans<'static>> {
+    let height = view.minimap.height as usize;
+    let len = view.current.items.len();
+    if height == 0 || len == 0 {
+        return Vec::new();
+    }
+
+    // a row covers several items if the list is longer than the minimap
+    let row = |idx: usize| if len <= height { idx } else { idx * height / len };
+    let rows = row(len - 1) + 1;
+
+    // markers of higher ranks hide lower ones in the same row
+    let mut markers = vec![(0, '│', theme.hint); rows];
+    let mut mark = |idx: usize, marker: (u8, char, Style)| {
+        let cell = &mut markers[row(idx)];
+        if marker.0 > cell.0 {
+            *cell = marker;
+        }
+    };
 
-    f.render_stateful_widget(list, chunk, &mut view.current.state);
+    let visible = view.current.offset..(view.current.offset + view.current.height).min(len);
+    for idx in visible {
+        mark(idx, (1, '┃', theme.border));
+    }
+    for (idx, _) in &view.matches.items {
+        mark(*idx, (2, '•', theme.matched));
+    }
+    for id in &view.prevs.items {
+        mark(view.index[id], (3, '▲', theme.prev));
+    }
+    for id in &view.nexts.items {
+        mark(view.index[id], (3, '▼', theme.next));
+    }
+    if let Some(idx) = view.current.state.selected() {
+        mark(idx, (4, '●', theme.selected));
+    }
+
+    markers
+        .into_iter()
+        .map(|(_, c, style)| Spans::from(Span::styled(c.to_string(), style)))
+        .collect()
 }
 
 fn draw_match<B: Backend>(f: &mut Frame<B>, chunk: Rect, view: &mut View) {
@@ -102,94 +263,453 @@ fn draw_match<B: Backend>(f: &mut Frame<B>, chunk: Rect, view: &mut View) {
     f.render_widget(block, chunk);
 }
 
-fn draw_adjacent<B: Backend>(f: &mut Frame<B>, chunk: Rect, view: &mut View) {
+fn draw_adjacent<B: Backend>(
+    f: &mut Frame<B>,
+    chunk: Rect,
+    view: &mut View,
+    panes: &Panes,
+    theme: &Theme,
+) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
         .split(chunk);
 
-    draw_prevs(f, chunks[0], view);
-    draw_nexts(f, chunks[1], view);
+    draw_prevs(f, chunks[0], view, panes, theme);
+    draw_nexts(f, chunks[1], view, panes, theme);
 }
 
-fn draw_prevs<B: Backend>(f: &mut Frame<B>, chunk: Rect, view: &mut View) {
-    let block = surrounding_block("Prev Nodes".to_string(), view.focus == Focus::Prev);
+fn draw_prevs<B: Backend>(
+    f: &mut Frame<B>,
+    chunk: Rect,
+    view: &mut View,
+    panes: &Panes,
+    theme: &Theme,
+) {
+    let focused = view.focus == Focus::Prev;
+    let block = surrounding_block("Prev Nodes".to_string(), focused, theme);
+
+    let fit = Fit::new(block.inner(chunk), view.prevs.shift, panes.elided);
+    let tooltip = tooltip(view, view.prevs.selected(), focused, &fit);
+    let (area, tooltip_area) = split_tooltip(block.inner(chunk), &tooltip);
+    view.prevs.frame(area);
 
     let list: Vec<ListItem> = (view.prevs.items.par_iter())
-        .map(|id| ListItem::new(vec![Spans::from(Span::raw(id.as_str()))]))
+        .map(|id| ListItem::new(adjacent(view, id, &fit, theme)))
         .collect();
 
-    let list = List::new(list)
-        .block(block)
-        .highlight_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
-        .highlight_symbol("> ");
+    let list = List::new(list).highlight_style(theme.prev_selected).highlight_symbol("> ");
 
-    f.render_stateful_widget(list, chunk, &mut view.prevs.state);
+    f.render_widget(block, chunk);
+    f.render_stateful_widget(list, area, &mut view.prevs.state);
+    draw_tooltip(f, tooltip_area, tooltip, theme);
 }
 
-fn draw_nexts<B: Backend>(f: &mut Frame<B>, chunk: Rect, view: &mut View) {
-    let block = surrounding_block("Next Nodes".to_string(), view.focus == Focus::Next);
+fn draw_nexts<B: Backend>(
+    f: &mut Frame<B>,
+    chunk: Rect,
+    view: &mut View,
+    panes: &Panes,
+    theme: &Theme,
+) {
+    let focused = view.focus == Focus::Next;
+    let block = surrounding_block("Next Nodes".to_string(), focused, theme);
+
+    let fit = Fit::new(block.inner(chunk), view.nexts.shift, panes.elided);
+    let tooltip = tooltip(view, view.nexts.selected(), focused, &fit);
+    let (area, tooltip_area) = split_tooltip(block.inner(chunk), &tooltip);
+    view.nexts.frame(area);
 
     let list: Vec<ListItem> = (view.nexts.items.par_iter())
-        .map(|id| ListItem::new(vec![Spans::from(Span::raw(id.as_str()))]))
+        .map(|id| ListItem::new(adjacent(view, id, &fit, theme)))
         .collect();
 
-    let list = List::new(list)
-        .block(block)
-        .highlight_style(Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD))
-        .highlight_symbol("> ");
+    let list = List::new(list).highlight_style(theme.next_selected).highlight_symbol("> ");
 
-    f.render_stateful_widget(list, chunk, &mut view.nexts.state);
+    f.render_widget(block, chunk);
+    f.render_stateful_widget(list, area, &mut view.nexts.state);
+    draw_tooltip(f, tooltip_area, tooltip, theme);
 }
 
-fn draw_metadata<B: Backend>(f: &mut Frame<B>, chunk: Rect, view: &mut View) {
-    let block = surrounding_block("Attrs".to_string(), false);
+/// An item of the prev or next list, annotated with the summary of its label and its degrees.
+/// Items rendered from the template are not annotated with the summary.
+fn adjacent(view: &View, id: &str, fit: &Fit, theme: &Theme) -> Spans<'static> {
+    let graph = &view.graph;
+    let text = glyphs(view.text(id), Style::default());
+    let mut notes = Vec::new();
+
+    let node = graph.search_node(&id.to_string()).unwrap();
+    let summary = label_lines(node).into_iter().find(|line| line != id);
+    if let Some(summary) = summary.filter(|_| view.template.is_none()) {
+        let summary = if summary.chars().count() > SUMMARY {
+            let summary: String = summary.chars().take(SUMMARY - 1).collect();
+            format!("{summary}…")
+        } else {
+            summary
+        };
+        notes.extend(glyphs(&format!("  {summary}"), theme.hint));
+    }
 
-    let id = view.current_id();
-    let node = view.graph.search_node(&id).unwrap();
+    let ins = graph.froms(&id.to_string()).map_or(0, |froms| froms.len());
+    let outs = graph.tos(&id.to_string()).map_or(0, |tos| tos.len());
+    notes.extend(glyphs(&format!("  (in: {ins} out: {outs})"), theme.hint));
 
-    let paragraph = Paragraph::new(pretty_metadata(node)).block(block).wrap(Wrap { trim: true });
+    fit.apply(text, notes)
+}
 
-    f.render_widget(paragraph, chunk);
+/// `Fit` fits items of a list into its width,
+/// eliding them in the middle, or scrolling them horizontally with `…` marking the hidden beginning.
+struct Fit {
+    /// Width of items, excluding the highlight symbol
+    width: usize,
+    shift: usize,
+    elided: bool,
 }
 
-fn pretty_metadata(node: &Node) -> String {
-    let mut metadata = String::new();
+impl Fit {
+    fn new(area: Rect, shift: usize, elided: bool) -> Self {
+        let width = (area.width as usize).saturating_sub(HIGHLIGHT);
+
+        Self { width, shift, elided }
+    }
+
+    /// Fit the item of `text` followed by `notes`, where only the text is elided.
+    fn apply(&self, mut text: Vec<Glyph>, notes: Vec<Glyph>) -> Spans<'static> {
+        let mut glyphs = if self.elided {
+            if text.len() > self.width && self.width > 1 {
+                let head = (self.width - 1) / 2;
+                let tail = self.width - 1 - head;
+                let ellipsis = ('…', text[head].1);
+                text.splice(head..text.len() - tail, [ellipsis]);
+            }
+            text.extend(notes);
+            text
+        } else {
+            text.extend(notes);
+            let shift = self.shift.min(text.len().saturating_sub(1));
+            if shift > 0 {
+                text.drain(..shift);
+                text[0].0 = '…';
+            }
+            text
+        };
+
+        // group characters of the same style into spans
+        let mut spans = Vec::new();
+        while let Some(&(_, style)) = glyphs.first() {
+            let len = glyphs.iter().take_while(|(_, s)| *s == style).count();
+            let content: String = glyphs.drain(..len).map(|(c, _)| c).collect();
+            spans.push(Span::styled(content, style));
+        }
+
+        Spans::from(spans)
+    }
 
-    let id = node.id();
-    writeln!(metadata, "[{id}]").unwrap();
-    writeln!(metadata).unwrap();
+    /// Whether `text` is not shown in full.
+    fn cuts(&self, text: &str) -> bool {
+        (self.shift > 0 && !self.elided) || text.chars().count() > self.width
+    }
+}
 
-    let attrs = node.attrs();
+/// A character with its style.
+type Glyph = (char, Style);
 
-    if let Some(label) = attrs.get("label") {
-        if label.is_html() {
-            let label = htmlparser::parse(label.value());
+fn glyphs(text: &str, style: Style) -> Vec<Glyph> {
+    text.chars().map(|c| (c, style)).collect()
+}
 
-            for data in label {
-                if data.starts_with("Input") {
-                    continue;
-                }
+/// Tooltip showing the `selected` item of the focused list in full if it is cut,
+/// followed by its id if the item is rendered from the template.
+fn tooltip(view: &View, selected: Option<String>, focused: bool, fit: &Fit) -> Option<String> {
+    let id = selected.filter(|_| focused)?;
+    let text = view.text(&id);
+
+    if !fit.cuts(text) {
+        return None;
+    }
+
+    if text == id {
+        Some(id)
+    } else {
+        Some(format!("{text}\n{id}"))
+    }
+}
+
+/// Split `area` into the area of items and the area of the tooltip at the bottom,
+/// taking up to a third of the area, with a line separating them.
+fn split_tooltip(area: Rect, tooltip: &Option<String>) -> (Rect, Rect) {
+    let tooltip = match tooltip {
+        Some(tooltip) if area.width > 0 => tooltip,
+        _ => return (area, Rect::default()),
+    };
+
+    let width = area.width as usize;
+    let lines: usize =
+        tooltip.lines().map(|line| 1 + (line.chars().count().max(1) - 1) / width).sum();
+    let height = (lines as u16 + 1).min(area.height / 3);
+    if height < 2 {
+        return (area, Rect::default());
+    }
+
+    let items = Rect { height: area.height - height, ..area };
+    let tooltip = Rect { y: area.y + items.height, height, ..area };
+
+    (items, tooltip)
+}
+
+fn draw_tooltip<B: Backend>(f: &mut Frame<B>, area: Rect, tooltip: Option<String>, theme: &Theme) {
+    if let Some(tooltip) = tooltip.filter(|_| area.height > 0) {
+        let block = Block::default().borders(Borders::TOP).border_style(theme.border);
+        let paragraph =
+            Paragraph::new(tooltip).block(block).style(theme.hint).wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, area);
+    }
+}
+
+fn draw_metadata<B: Backend>(
+    f: &mut Frame<B>,
+    chunk: Rect,
+    view: &mut View,
+    panes: &Panes,
+    theme: &Theme,
+) {
+    let id = view.previewed_id();
+
+    let title = format!("Attrs ({}) {}", panes.attrs.name(), view.membership(&id));
+    let block = surrounding_block(title, view.focus == Focus::Attrs, theme);
+    let inner = block.inner(chunk);
+
+    if panes.attrs == AttrsMode::Label {
+        let node = view.graph.search_node(&id).unwrap();
+        let label = node.attrs().get("label").filter(|label| label.is_html());
+
+        if let Some(rows) = label.and_then(|label| htmlparser::parse_table(label.value())) {
+            f.render_widget(block, chunk);
+            draw_html_table(f, inner, view, &id, &rows, theme);
+            return;
+        }
+    }
 
-                let values = data.split("\\l");
-                for value in values {
-                    writeln!(metadata, "{value}").unwrap();
+    let lines = match panes.attrs {
+        AttrsMode::Label => label(view, &id, theme),
+        AttrsMode::Table => table(view, &id, theme),
+        AttrsMode::Raw => raw(view, &id),
+    };
+
+    // lines are wrapped beforehand, such that the panel knows how far it can scroll
+    let width = inner.width as usize;
+    let lines: Vec<Spans> = lines.into_iter().flat_map(|line| wrap(line, width)).collect();
+    view.attrs.frame(inner, lines.len());
+
+    let lines: Vec<Spans> = lines.into_iter().skip(view.attrs.offset).collect();
+    let paragraph = Paragraph::new(lines).block(block);
+
+    f.render_widget(paragraph, chunk);
+}
+
+/// Draw an HTML-like table label of the node `id` as a table,
+/// with the nodes linked through its ports.
+/// While previewing a prev or next node, ports linked to the current node are highlighted.
+fn draw_html_table<B: Backend>(
+    f: &mut Frame<B>,
+    chunk: Rect,
+    view: &mut View,
+    id: &str,
+    rows: &[Vec<htmlparser::Cell>],
+    theme: &Theme,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)].as_ref())
+        .split(chunk);
+
+    f.render_widget(Paragraph::new(header(view, id, theme)), chunks[0]);
+
+    let ports = view.ports(id).clone();
+    let current = view.current_id();
+
+    let cell = |cell: &htmlparser::Cell| -> (String, Style) {
+        match cell.port.as_ref().map(|port| ports.get(port)) {
+            Some(Some((froms, tos))) => {
+                let mut text = cell.text.clone();
+                if !froms.is_empty() {
+                    write!(text, " ←{}", froms.join(",")).unwrap();
                 }
+                if !tos.is_empty() {
+                    write!(text, " →{}", tos.join(",")).unwrap();
+                }
+
+                let style = match &view.focus {
+                    Focus::Prev if tos.contains(&current) => theme.prev_selected,
+                    Focus::Next if froms.contains(&current) => theme.next_selected,
+                    _ => theme.accent,
+                };
+
+                (text, style)
             }
-        } else {
-            let values = label.value().split("\\l");
-            for value in values {
-                writeln!(metadata, "{value}").unwrap();
+            Some(None) => (cell.text.clone(), theme.accent),
+            None => (cell.text.clone(), Style::default()),
+        }
+    };
+
+    let grid: Vec<Vec<Option<(String, Style)>>> = (htmlparser::grid(rows).into_iter())
+        .map(|row| row.into_iter().map(|slot| slot.map(cell)).collect())
+        .collect();
+
+    // spanning cells are drawn in their first column, as `tui` tables cannot span cells
+    let mut widths = vec![0; grid.first().map_or(0, Vec::len)];
+    for row in &grid {
+        for (c, slot) in row.iter().enumerate() {
+            if let Some((text, _)) = slot {
+                widths[c] = widths[c].max(text.chars().count());
             }
         }
-    } else {
-        for attr in attrs {
-            let key = attr.key();
-            let value = attr.value();
+    }
+
+    view.attrs.frame(chunks[1], grid.len());
+
+    let rows: Vec<Row> = (grid.into_iter().skip(view.attrs.offset))
+        .map(|row| {
+            Row::new(row.into_iter().map(|slot| match slot {
+                Some((text, style)) => Cell::from(text).style(style),
+                None => Cell::from(""),
+            }))
+        })
+        .collect();
+    let widths: Vec<Constraint> = widths.iter().map(|&w| Constraint::Length(w as u16)).collect();
+
+    let table = Table::new(rows).widths(&widths).column_spacing(TABLE_SPACING);
+
+    f.render_widget(table, chunks[1]);
+}
+
+/// Header of the attrs panel, telling whether the node `id` is a previewed prev or next node.
+fn header(view: &View, id: &str, theme: &Theme) -> Spans<'static> {
+    let mut header = vec![Span::styled(format!("[{id}]"), theme.header)];
+
+    match &view.focus {
+        Focus::Prev if !view.prevs.items.is_empty() => {
+            header.push(Span::styled(" (prev node)", theme.hint));
+        }
+        Focus::Next if !view.nexts.items.is_empty() => {
+            header.push(Span::styled(" (next node)", theme.hint));
+        }
+        _ => {}
+    }
+
+    Spans::from(header)
+}
 
-            writeln!(metadata, "{key} : {value}").unwrap();
+/// Decoded label of the node `id`, which is its id if not given,
+/// followed by the edges from or to the current node if previewed.
+fn label(view: &View, id: &str, theme: &Theme) -> Vec<Spans<'static>> {
+    let node = view.graph.search_node(&id.to_string()).unwrap();
+
+    let mut lines = vec![header(view, id, theme), Spans::default()];
+    lines.extend(label_lines(node).into_iter().map(Spans::from));
+    lines.extend(edges(view, theme));
+
+    lines
+}
+
+/// All attributes of the node `id`,
+/// followed by those of its enclosing subgraphs from the innermost one,
+/// and the edges from or to the current node if previewed.
+fn table(view: &View, id: &str, theme: &Theme) -> Vec<Spans<'static>> {
+    let node = view.graph.search_node(&id.to_string()).unwrap();
+
+    let mut lines = vec![header(view, id, theme)];
+    lines.extend(attrs(node.attrs(), theme));
+
+    for subgraph in view.enclosing(id).into_iter().rev() {
+        if subgraph.attrs().is_empty() {
+            continue;
+        }
+
+        let title = format!("[{}] (inherited)", subgraph.id());
+        lines.push(Spans::default());
+        lines.push(Spans::from(Span::styled(title, theme.header)));
+        lines.extend(attrs(subgraph.attrs(), theme));
+    }
+
+    lines.extend(edges(view, theme));
+
+    lines
+}
+
+/// Attributes of the edges between the current node and the previewed node.
+fn edges(view: &View, theme: &Theme) -> Vec<Spans<'static>> {
+    let mut lines = Vec::new();
+
+    for edge in view.previewed_edges() {
+        let mut raw = Vec::new();
+        edge.id().to_dot(0, &mut raw).expect("to_dot should succeed");
+        let title = format!("[{}] (edge)", String::from_utf8_lossy(&raw).trim());
+
+        lines.push(Spans::default());
+        lines.push(Spans::from(Span::styled(title, theme.header)));
+        lines.extend(attrs(edge.attrs(), theme));
+    }
+
+    lines
+}
+
+/// Attributes sorted by keys, with the keys aligned.
+fn attrs(attrs: &HashSet<Attr>, theme: &Theme) -> Vec<Spans<'static>> {
+    let mut attrs: Vec<&Attr> = attrs.iter().collect();
+    attrs.sort_unstable_by_key(|attr| attr.key());
+
+    let width = attrs.iter().map(|attr| attr.key().chars().count()).max().unwrap_or(0);
+
+    (attrs.iter())
+        .map(|attr| {
+            let key = Span::styled(format!("{:width$}", attr.key()), theme.accent);
+            Spans::from(vec![key, Span::raw(" : "), Span::raw(attr.value().clone())])
+        })
+        .collect()
+}
+
+/// Raw dot source of the node `id`,
+/// followed by the edges from or to the current node if previewed.
+fn raw(view: &View, id: &str) -> Vec<Spans<'static>> {
+    let mut raw = view.raw(id);
+    for edge in view.previewed_edges() {
+        let mut buffer = Vec::new();
+        edge.to_dot(0, &mut buffer).expect("to_dot should succeed");
+        raw.push('\n');
+        raw.push_str(&String::from_utf8_lossy(&buffer));
+    }
+
+    raw.lines().map(|line| Spans::from(line.replace('\t', "    "))).collect()
+}
+
+/// Wrap `line` at `width` characters, and at line breaks in it.
+fn wrap(line: Spans<'static>, width: usize) -> Vec<Spans<'static>> {
+    let mut lines = vec![Vec::new()];
+    let mut len = 0;
+
+    for span in line.0 {
+        let mut text = String::new();
+
+        for c in span.content.chars() {
+            if c == '\n' || (width > 0 && len == width) {
+                lines.last_mut().unwrap().push(Span::styled(text, span.style));
+                lines.push(Vec::new());
+                text = String::new();
+                len = 0;
+
+                if c == '\n' {
+                    continue;
+                }
+            }
+
+            text.push(c);
+            len += 1;
         }
+
+        lines.last_mut().unwrap().push(Span::styled(text, span.style));
     }
 
-    metadata
+    lines.into_iter().map(Spans::from).collect()
 }
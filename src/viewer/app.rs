@@ -1,18 +1,31 @@
 use crate::viewer::{
-    command::{Command, CommandTrie},
+    command::{Command, CommandTrie, Where},
+    config::Config,
     error::{DotViewerError, DotViewerResult},
     help,
+    keymap::{Action, Chord, Keymap},
     modes::{Mode, PopupMode, SearchMode},
+    panes::Panes,
     success::Success,
-    utils::{Input, List, Table, Tabs},
-    view::View,
+    theme::Theme,
+    utils::{
+        parse_number, Clipboard, Derivation, Fields, Input, Job, Lineage, List, Marks, Messages,
+        Op, Paint, Sketch, Table, Tabs, Task, Template,
+    },
+    view::{Focus, Search, View},
 };
 
+use std::collections::HashMap;
 use std::fs;
+use std::time::{Duration, Instant};
 
 use graphviz_rs::prelude::*;
 
-use crossterm::event::KeyCode;
+use log::warn;
+
+/// Time to wait for a background job before leaving it running, such that quick jobs
+/// finish in place without flashing a spinner
+const GRACE: Duration = Duration::from_millis(50);
 
 /// `App` holds `dot-viewer` application states.
 ///
@@ -28,45 +41,174 @@ pub(crate) struct App {
     /// Result of the last command that was made
     pub result: DotViewerResult<Success>,
 
+    /// Log of past results and errors, shown in messages popup
+    pub messages: Messages,
+
+    /// Path to the dot file being viewed
+    pub path: String,
+
     /// Tabs to be shown in the main screen
     pub tabs: Tabs<View>,
 
     /// Input form to be shown in the main screen
     pub input: Input,
 
-    /// Most recent key event
-    pub lookback: Option<KeyCode>,
+    /// Background job deriving a new tab, e.g., by `neighbors` or `filter`
+    pub job: Option<Job<View>>,
+
+    /// Background job searching the current tab for the input
+    pub search: Option<Job<Search>>,
+
+    /// Background job copying a yanked text, e.g., by piping it to the clipboard command
+    pub yank: Option<Job<String>>,
+
+    /// Keybindings of each mode
+    pub keymap: Keymap,
+
+    /// Styles of the user interface
+    pub theme: Theme,
+
+    /// Layout of panes in the main screen
+    pub panes: Panes,
+
+    /// Template rendering the items of node lists, if shown instead of ids
+    pub template: Template,
+
+    /// Keys typed so far, while they are a prefix of some keybindings, e.g., `g` of `gg`
+    pub keys: Vec<Chord>,
+
+    /// Action waiting for a character argument, e.g., the name of a mark after `m`
+    pub pending: Option<Action>,
+
+    /// Pending count prefix for the next motion, e.g., `5` in `5j`
+    pub count: Option<usize>,
 
     /// Autocomplete support for commands
     pub trie: CommandTrie,
 
+    /// Clipboard that yanked texts are copied to
+    pub clipboard: Clipboard,
+
     /// Keybinding helps
     pub help: Table,
+
+    /// Extractors of numeric fields for `where` command
+    pub fields: Fields,
+
+    /// Marks on nodes, persisted per dot file
+    pub marks: Marks,
+
+    /// Derivation tree of tabs, shown in tabs popup
+    pub lineage: Lineage,
+
+    /// Colors of nodes in the current tab with their counts, shown in legend popup
+    pub legend: List<(Paint, usize)>,
+
+    /// Sketch of the neighborhood of a node, shown in sketch popup
+    pub sketch: Option<Sketch>,
+
+    /// Most recent mouse click, as its instant, column and row, to detect double clicks
+    pub click: Option<(Instant, u16, u16)>,
 }
 
 impl App {
-    /// Constructs a new `App`, given a `path` to a dot format DAG.
-    pub fn new(path: &str) -> DotViewerResult<Self> {
+    /// Constructs a new `App`, given a `path` to a dot format DAG and user `config`.
+    pub fn new(path: &str, config: Config) -> DotViewerResult<Self> {
         let quit = false;
 
         let mode = Mode::Normal;
 
         let result: DotViewerResult<Success> = Ok(Success::default());
 
-        let graph = parser::parse_from_file(path)?;
+        let messages = Messages::default();
+
+        let path = path.to_string();
+
+        let graph = parser::parse_from_file(&path)?;
 
         let view = View::new(graph.id().clone(), graph)?;
         let tabs = Tabs::from_iter(vec![view]);
 
         let input = Input::default();
 
-        let lookback = None;
+        let job = None;
 
-        let trie = CommandTrie::new();
+        let search = None;
+
+        let yank = None;
+
+        let keymap = Keymap::new(&config.keys)?;
+
+        let theme = Theme::new(&config.theme)?;
+
+        let panes = Panes::load().unwrap_or_else(|e| {
+            warn!("failed to load layout, {e}");
+            Panes::default()
+        });
+
+        let template = match &config.template {
+            Some(template) => template.parse()?,
+            None => Template::default(),
+        };
+
+        let keys = Vec::new();
 
-        let help = Table::new(help::HEADER, help::ROWS);
+        let pending = None;
 
-        Ok(Self { quit, mode, result, tabs, input, lookback, trie, help })
+        let count = None;
+
+        let trie = CommandTrie::new();
+
+        let clipboard = Clipboard::new(&config.clipboard);
+
+        let help = Table::new(help::HEADER, help::rows(&keymap));
+
+        let fields = Fields::new(&config.fields)?;
+
+        let marks = Marks::load(&path).unwrap_or_else(|e| {
+            warn!("failed to load marks, {e}");
+            Marks::default()
+        });
+
+        let lineage = Lineage::default();
+
+        let legend = List::from_iter(Vec::new());
+
+        let sketch = None;
+
+        let click = None;
+
+        let mut app = Self {
+            quit,
+            mode,
+            result,
+            messages,
+            path,
+            tabs,
+            input,
+            job,
+            search,
+            yank,
+            keymap,
+            theme,
+            panes,
+            template,
+            keys,
+            pending,
+            count,
+            trie,
+            clipboard,
+            help,
+            fields,
+            marks,
+            lineage,
+            legend,
+            sketch,
+            click,
+        };
+        app.apply_template();
+
+        Ok(app)
     }
 
     /// Navigate to the next match.
@@ -83,63 +225,358 @@ impl App {
         view.goto_match()
     }
 
-    /// Navigate to the first.
+    /// Navigate to the first, or to the `count`-th with a count prefix.
     pub fn goto_first(&mut self) -> DotViewerResult<()> {
-        if let Some(KeyCode::Char('g')) = self.lookback {
-            let view = self.tabs.selected();
-            view.goto_first()?;
+        let idx = self.take_count() - 1;
+        self.goto_nth(idx)
+    }
+
+    /// Navigate to the last, or to the `count`-th with a count prefix.
+    pub fn goto_last(&mut self) -> DotViewerResult<()> {
+        let idx = self.count.take().map_or(usize::MAX, |count| count - 1);
+        self.goto_nth(idx)
+    }
+
+    /// Navigate to the `count` percent of the focused list.
+    pub fn goto_percent(&mut self) -> DotViewerResult<()> {
+        let percent = (self.count.take())
+            .ok_or(DotViewerError::ViewerError("no count given for %".to_string()))?;
+
+        let len = self.focused_len()?;
+        let idx = (percent.min(100) * len + 50) / 100;
+        self.goto_nth(idx.saturating_sub(1))
+    }
+
+    /// Scroll the focused list by `ratio` of its height, e.g., `0.5` for half a page down,
+    /// repeated `count` times with a count prefix.
+    pub fn scroll(&mut self, ratio: f32) -> DotViewerResult<()> {
+        let pages = self.take_count() as f32;
+        let height = self.focused_height()?.max(1) as f32;
+        let offset = (height * ratio * pages) as isize;
+
+        self.scroll_by(offset)
+    }
+
+    /// Scroll the focused list by `offset` items, stopping at the first and the last.
+    pub fn scroll_by(&mut self, offset: isize) -> DotViewerResult<()> {
+        match &self.mode {
+            Mode::Normal => self.tabs.selected().scroll(offset)?,
+            Mode::Popup(pmode) => match pmode {
+                PopupMode::Tree | PopupMode::TreeFilter => {
+                    self.tabs.selected().subtree.scroll(offset)
+                }
+                PopupMode::Marks => self.marks.list.scroll(offset),
+                PopupMode::Lineage => self.lineage.list.scroll(offset),
+                PopupMode::Legend => self.legend.scroll(offset),
+                PopupMode::Messages => self.messages.list.scroll(offset),
+                PopupMode::Sketch => self.sketch_mut()?.scroll(offset),
+                PopupMode::Help => self.help.scroll(offset),
+            },
+            _ => Err(DotViewerError::ViewerError("no list focused".to_string()))?,
         }
 
         Ok(())
     }
 
-    /// Navigate to the last.
-    pub fn goto_last(&mut self) -> DotViewerResult<()> {
+    /// Navigate to the `idx`-th in the focused list, or the last if out of range.
+    fn goto_nth(&mut self, idx: usize) -> DotViewerResult<()> {
+        match &self.mode {
+            Mode::Normal => self.tabs.selected().goto_nth(idx)?,
+            Mode::Popup(pmode) => match pmode {
+                PopupMode::Tree | PopupMode::TreeFilter => self.tabs.selected().subtree.nth(idx),
+                PopupMode::Marks => self.marks.list.nth(idx),
+                PopupMode::Lineage => self.lineage.list.nth(idx),
+                PopupMode::Legend => self.legend.nth(idx),
+                PopupMode::Messages => self.messages.list.nth(idx),
+                PopupMode::Sketch => self.sketch_mut()?.nth(idx),
+                PopupMode::Help => self.help.nth(idx),
+            },
+            _ => Err(DotViewerError::ViewerError("no list focused".to_string()))?,
+        }
+
+        Ok(())
+    }
+
+    fn focused_len(&mut self) -> DotViewerResult<usize> {
+        match &self.mode {
+            Mode::Normal => Ok(self.tabs.selected().len()),
+            Mode::Popup(pmode) => match pmode {
+                PopupMode::Tree | PopupMode::TreeFilter => Ok(self.tabs.selected().subtree.len()),
+                PopupMode::Marks => Ok(self.marks.list.items.len()),
+                PopupMode::Lineage => Ok(self.lineage.list.items.len()),
+                PopupMode::Legend => Ok(self.legend.items.len()),
+                PopupMode::Messages => Ok(self.messages.list.items.len()),
+                PopupMode::Sketch => Ok(self.sketch_mut()?.len()),
+                PopupMode::Help => Ok(self.help.rows.len()),
+            },
+            _ => Err(DotViewerError::ViewerError("no list focused".to_string())),
+        }
+    }
+
+    fn focused_height(&mut self) -> DotViewerResult<usize> {
+        match &self.mode {
+            Mode::Normal => Ok(self.tabs.selected().height()),
+            Mode::Popup(pmode) => match pmode {
+                PopupMode::Tree | PopupMode::TreeFilter => Ok(self.tabs.selected().subtree.height),
+                PopupMode::Marks => Ok(self.marks.list.height),
+                PopupMode::Lineage => Ok(self.lineage.list.height),
+                PopupMode::Legend => Ok(self.legend.height),
+                PopupMode::Messages => Ok(self.messages.list.height),
+                PopupMode::Sketch => Ok(self.sketch_mut()?.height),
+                PopupMode::Help => Ok(self.help.height),
+            },
+            _ => Err(DotViewerError::ViewerError("no list focused".to_string())),
+        }
+    }
+
+    /// Append a digit to the pending count prefix.
+    pub fn push_count(&mut self, digit: u32) {
+        let count = self.count.unwrap_or(0);
+        self.count = Some(count.saturating_mul(10).saturating_add(digit as usize));
+    }
+
+    /// Take the pending count prefix, which is 1 if not given.
+    pub fn take_count(&mut self) -> usize {
+        self.count.take().unwrap_or(1)
+    }
+
+    /// Navigate along the chain of unique successors (or predecessors if not `forward`),
+    /// `count` times.
+    pub fn goto_chain(&mut self, forward: bool) -> DotViewerResult<()> {
+        let n = self.take_count();
+        let view = self.tabs.selected();
+
+        (0..n).try_for_each(|_| view.goto_chain(forward))
+    }
+
+    /// Navigate to the nearest sink (or source if not `forward`).
+    pub fn goto_terminal(&mut self, forward: bool) -> DotViewerResult<()> {
+        let view = self.tabs.selected();
+        view.goto_terminal(forward)
+    }
+
+    /// Navigate back in the jump list.
+    pub fn goto_jump_back(&mut self) -> DotViewerResult<()> {
+        let view = self.tabs.selected();
+        view.goto_jump_back()
+    }
+
+    /// Navigate forward in the jump list.
+    pub fn goto_jump_forward(&mut self) -> DotViewerResult<()> {
+        let view = self.tabs.selected();
+        view.goto_jump_forward()
+    }
+
+    /// Navigate to the node visited right before the latest jump.
+    pub fn goto_jump_last(&mut self) -> DotViewerResult<()> {
+        let view = self.tabs.selected();
+        view.goto_jump_last()
+    }
+
+    /// Mark the currently selected node as `name`.
+    pub fn set_mark(&mut self, name: char) -> DotViewerResult<()> {
+        let view = self.tabs.selected();
+        let id = view.current_id();
+
+        self.marks.set(name, id)
+    }
+
+    /// Navigate to the node marked as `name`.
+    /// If the current tab does not contain the node, switch to the first tab containing it.
+    pub fn goto_mark(&mut self, name: char) -> DotViewerResult<()> {
+        let id = (self.marks.get(name))
+            .ok_or(DotViewerError::ViewerError(format!("no such mark {name:?}")))?;
+
+        if !self.tabs.selected().index.contains_key(&id) {
+            let idx = (self.tabs.tabs.iter())
+                .position(|view| view.index.contains_key(&id))
+                .ok_or(DotViewerError::ViewerError(format!("no tab contains node {id:?}")))?;
+            self.tabs.select(idx);
+        }
+
+        let view = self.tabs.selected();
+        view.goto(&id)
+    }
+
+    /// Navigate to the node of the selected mark in marks popup.
+    pub fn goto_selected_mark(&mut self) -> DotViewerResult<()> {
+        let (name, _) = (self.marks.list.selected())
+            .ok_or(DotViewerError::ViewerError("no mark selected".to_string()))?;
+
+        self.set_normal_mode();
+        self.goto_mark(name)
+    }
+
+    /// Change the layout of panes, and remember it.
+    /// Failing to remember the layout is only logged, as the change is applied anyway.
+    pub fn relayout(&mut self, change: impl FnOnce(&mut Panes)) {
+        change(&mut self.panes);
+
+        // hidden panes cannot be focused
+        for view in &mut self.tabs.tabs {
+            if !self.panes.shows(&view.focus) {
+                view.focus = Focus::Current;
+            }
+        }
+
+        if let Err(e) = self.panes.save() {
+            warn!("failed to save layout, {e}");
+        }
+    }
+
+    /// Show items of node lists rendered from the template, or ids.
+    pub fn toggle_labels(&mut self) {
+        self.relayout(|panes| panes.labeled = !panes.labeled);
+        self.apply_template();
+    }
+
+    /// Change the template, showing items of node lists rendered from it.
+    pub fn set_template(&mut self, template: &str) -> DotViewerResult<()> {
+        self.set_normal_mode();
+
+        self.template = template.parse()?;
+        self.relayout(|panes| panes.labeled = true);
+        self.apply_template();
+
+        Ok(())
+    }
+
+    fn apply_template(&mut self) {
+        let template = if self.panes.labeled { Some(&self.template) } else { None };
+
+        for view in &mut self.tabs.tabs {
+            view.set_template(template);
+        }
+    }
+
+    /// Record the currently selected node in the jump list.
+    pub fn record_jump(&mut self) {
         let view = self.tabs.selected();
-        view.goto_last()
+        let id = view.current_id();
+        view.jumps.record(&id);
     }
 
-    /// Update search matches with trie.
+    /// Update search matches with trie, on a background job replacing the previous one.
     pub fn update_search(&mut self) {
         match &self.mode {
             Mode::Search(smode) => {
-                let view = self.tabs.selected();
+                if let Some(job) = self.search.take() {
+                    job.cancel();
+                }
+
                 let key = &self.input.key;
+                let task = self.tabs.selected().search(smode.clone(), key);
+                let job = Job::spawn(format!("search {key}"), task);
 
-                match smode {
-                    SearchMode::Fuzzy => view.update_fuzzy(key),
-                    SearchMode::Regex => view.update_regex(key),
+                match job.wait(GRACE) {
+                    Some(search) => self.show_search(search),
+                    None => self.search = Some(job),
                 }
-                view.update_trie();
-
-                // ignore goto errors while updating search matches
-                let _ = view.goto_match();
             }
             _ => unreachable!(),
         }
     }
 
-    /// Autocomplete user input.
-    pub fn autocomplete_fuzzy(&mut self) {
-        let view = self.tabs.selected();
+    /// Wait for the background search, if any, to show its matches.
+    pub fn settle_search(&mut self) {
+        if let Some(job) = self.search.take() {
+            self.show_search(job.join());
+        }
+    }
 
-        let key = &self.input.key;
-        if let Some(key) = view.autocomplete(key) {
-            view.update_fuzzy(&key);
-            view.update_trie();
-            self.input.set(key);
+    fn show_search(&mut self, search: DotViewerResult<Search>) {
+        let search = match search {
+            Ok(search) => search,
+            Err(e) => {
+                warn!("{e}");
+                return;
+            }
+        };
+
+        let searching = matches!(self.mode, Mode::Search(_));
+
+        if let Some(view) = self.tabs.tabs.iter_mut().find(|view| view.id == search.view) {
+            view.set_search(search);
+
+            // ignore goto errors while updating search matches
+            if searching {
+                let _ = view.select_match();
+            }
         }
     }
 
+    /// Whether any background job is running.
+    pub fn busy(&self) -> bool {
+        self.job.is_some() || self.search.is_some() || self.yank.is_some()
+    }
+
+    /// Collect the results of background jobs that are done, on every tick of the event loop.
+    pub fn tick(&mut self) {
+        if let Some(view) = self.job.as_ref().and_then(|job| job.poll()) {
+            self.job = None;
+            self.result = view.map(|view| {
+                self.tabs.open(view);
+                Success::default()
+            });
+            self.log_result();
+        }
+
+        if let Some(search) = self.search.as_ref().and_then(|job| job.poll()) {
+            self.search = None;
+            self.show_search(search);
+        }
+
+        if let Some(yanked) = self.yank.as_ref().and_then(|job| job.poll()) {
+            self.yank = None;
+            self.result = yanked.map(Success::YankSuccess);
+            self.log_result();
+        }
+    }
+
+    /// Go back to Normal mode, cancelling the background job started in the mode left,
+    /// i.e., deriving a tab in Normal mode or searching in Search mode.
+    pub fn cancel(&mut self) -> Success {
+        let cancelled = match &self.mode {
+            Mode::Normal => self.job.take().map(Job::cancel),
+            Mode::Search(_) => self.search.take().map(Job::cancel),
+            _ => None,
+        };
+
+        self.set_normal_mode();
+
+        cancelled.map_or(Success::default(), Success::CancelSuccess)
+    }
+
+    /// Derive a new tab by `task` on a background job named `name`, opening it once done.
+    fn derive(&mut self, name: String, task: Task<View>) -> DotViewerResult<()> {
+        self.set_normal_mode();
+
+        if let Some(job) = &self.job {
+            let msg = format!("{} is running, press esc to cancel", job.name);
+            return Err(DotViewerError::ViewerError(msg));
+        }
+
+        let job = Job::spawn(name, task);
+        match job.wait(GRACE) {
+            Some(view) => self.tabs.open(view?),
+            None => self.job = Some(job),
+        }
+
+        Ok(())
+    }
+
     /// Autocomplete user input.
-    pub fn autocomplete_regex(&mut self) {
+    /// Completions come from the matches of the current input, so its search is waited for,
+    /// while the completed input is searched on a background job as typed inputs are.
+    pub fn autocomplete_search(&mut self) {
+        self.settle_search();
+
         let view = self.tabs.selected();
 
         let key = &self.input.key;
         if let Some(key) = view.autocomplete(key) {
-            view.update_regex(&key);
-            view.update_trie();
             self.input.set(key);
+            self.update_search();
         }
     }
 
@@ -171,6 +608,7 @@ impl App {
             Command::Export(export) => self.export(export.filename),
             Command::Xdot(xdot) => self.xdot(xdot.filename),
             Command::Filter => self.filter().map(|_| Success::default()),
+            Command::Where(where_) => self.filter_where(where_).map(|_| Success::default()),
             Command::Help => {
                 self.set_popup_mode(PopupMode::Help);
                 Ok(Success::default())
@@ -179,6 +617,63 @@ impl App {
                 self.set_popup_mode(PopupMode::Tree);
                 Ok(Success::default())
             }
+            Command::Marks => {
+                self.set_popup_mode(PopupMode::Marks);
+                Ok(Success::default())
+            }
+            Command::Split(split) => {
+                self.set_normal_mode();
+
+                let other = match split.position {
+                    Some(0) => {
+                        let msg = "tab positions start from 1".to_string();
+                        return Err(DotViewerError::CommandError(msg));
+                    }
+                    position => position.map(|position| position - 1),
+                };
+                self.split(other).map(|_| Success::default())
+            }
+            Command::Tabs => {
+                self.show_lineage();
+                Ok(Success::default())
+            }
+            Command::Legend => {
+                self.show_legend();
+                Ok(Success::default())
+            }
+            Command::Yank(yank) => self.yank(yank.target.as_deref().unwrap_or("id")),
+            Command::Messages => {
+                self.set_popup_mode(PopupMode::Messages);
+                Ok(Success::default())
+            }
+            Command::Sketch(sketch) => {
+                self.sketch(sketch.depth.unwrap_or(2)).map(|_| Success::default())
+            }
+            Command::Template(template) => template.template.map_or(
+                Err(DotViewerError::CommandError("No argument supplied for template".to_string())),
+                |template| self.set_template(&template).map(|_| Success::default()),
+            ),
+            Command::Rename(rename) => rename.title.map_or(
+                Err(DotViewerError::CommandError("No argument supplied for rename".to_string())),
+                |title| self.rename(title).map(|_| Success::default()),
+            ),
+            Command::Move(move_) => move_.position.map_or(
+                Err(DotViewerError::CommandError("No argument supplied for move".to_string())),
+                |position| self.move_tab(position).map(|_| Success::default()),
+            ),
+            Command::Duplicate => self.duplicate().map(|_| Success::default()),
+            Command::Close => {
+                self.set_normal_mode();
+                self.tabs.close().map(|_| Success::default())
+            }
+            Command::Only => {
+                self.set_normal_mode();
+                self.tabs.close_others().map(|_| Success::default())
+            }
+            Command::Reopen => {
+                self.set_normal_mode();
+                self.tabs.reopen().map(|_| Success::default())
+            }
             Command::Quit => {
                 self.quit = true;
                 Ok(Success::default())
@@ -196,15 +691,81 @@ impl App {
     /// with specified depth.
     /// It opens a new tab with the neighbor graph view.
     pub fn neighbors(&mut self, depth: usize) -> DotViewerResult<()> {
+        let task = self.tabs.selected().neighbors(depth);
+
+        self.derive(format!("neighbors {depth}"), task)
+    }
+
+    /// Rename the currently selected tab.
+    pub fn rename(&mut self, title: String) -> DotViewerResult<()> {
+        self.set_normal_mode();
+
+        self.tabs.selected().title = title;
+
+        Ok(())
+    }
+
+    /// Move the currently selected tab to `position`, counting from 1.
+    pub fn move_tab(&mut self, position: usize) -> DotViewerResult<()> {
+        self.set_normal_mode();
+
+        if position == 0 {
+            return Err(DotViewerError::CommandError("tab positions start from 1".to_string()));
+        }
+
+        self.tabs.move_to(position - 1)
+    }
+
+    /// Duplicate the currently selected tab, at the current node.
+    /// It opens a new tab with the copied view.
+    pub fn duplicate(&mut self) -> DotViewerResult<()> {
+        let task = self.tabs.selected().duplicate();
+
+        self.derive("duplicate".to_string(), task)
+    }
+
+    /// Sketch the neighborhood of the currently selected node with specified depth.
+    /// It opens a popup with the sketch.
+    pub fn sketch(&mut self, depth: usize) -> DotViewerResult<()> {
         self.set_normal_mode();
 
-        let view_current = self.tabs.selected();
-        let view_new = view_current.neighbors(depth)?;
-        self.tabs.open(view_new);
+        let view = self.tabs.selected();
+        let sketch = Sketch::new(&view.graph, &view.current_id(), depth)?;
+        self.sketch = Some(sketch);
+
+        self.set_popup_mode(PopupMode::Sketch);
 
         Ok(())
     }
 
+    /// Navigate to the node selected in the sketch, sketching its neighborhood.
+    pub fn goto_sketched(&mut self) -> DotViewerResult<()> {
+        let sketch = self.sketch_mut()?;
+        let (id, depth) = (sketch.selected_id(), sketch.depth);
+
+        let view = self.tabs.selected();
+        view.goto(&id)?;
+
+        self.sketch(depth)
+    }
+
+    /// Sketch the neighborhood of the same center with depth changed by `delta`.
+    pub fn resketch(&mut self, delta: isize) -> DotViewerResult<()> {
+        let sketch = self.sketch_mut()?;
+        let depth = sketch.depth.saturating_add_signed(delta).max(1);
+        let center = sketch.center.clone();
+
+        let view = self.tabs.selected();
+        let sketch = Sketch::new(&view.graph, &center, depth)?;
+        self.sketch = Some(sketch);
+
+        Ok(())
+    }
+
+    pub fn sketch_mut(&mut self) -> DotViewerResult<&mut Sketch> {
+        (self.sketch.as_mut()).ok_or(DotViewerError::ViewerError("no sketch drawn".to_string()))
+    }
+
     /// Export the current view to dot.
     pub fn export(&mut self, filename: Option<String>) -> DotViewerResult<Success> {
         self.set_normal_mode();
@@ -241,13 +802,35 @@ impl App {
     /// Apply filter on the current view, based on the current matches.
     /// Opens a new tab with the filtered view.
     pub fn filter(&mut self) -> DotViewerResult<()> {
+        let view = self.tabs.selected();
+        let name = format!("filter {}", view.key);
+        let task = view.filter();
+
+        self.derive(name, task)
+    }
+
+    /// Apply numeric filter on the current view, e.g., `where D > 64`.
+    /// Opens a new tab with the filtered view.
+    pub fn filter_where(&mut self, where_: Where) -> DotViewerResult<()> {
         self.set_normal_mode();
 
-        let view_current = self.tabs.selected();
-        let view_new = view_current.filter()?;
-        self.tabs.open(view_new);
+        let (field, op, value) = match where_ {
+            Where { field: Some(field), op: Some(op), value: Some(value) } => (field, op, value),
+            _ => {
+                let msg = "Usage: where [field] [op] [value]".to_string();
+                return Err(DotViewerError::CommandError(msg));
+            }
+        };
 
-        Ok(())
+        let op: Op = op.parse()?;
+        let value = parse_number(&value)
+            .ok_or(DotViewerError::CommandError(format!("invalid number {value}")))?;
+        let regex = self.fields.regex(&field);
+
+        let name = format!("where {field} {op} {value}");
+        let task = self.tabs.selected().filter_where(&field, &regex, op, value);
+
+        self.derive(name, task)
     }
 
     /// Extract a subgraph from the current view.
@@ -256,13 +839,198 @@ impl App {
     pub fn subgraph(&mut self) -> DotViewerResult<()> {
         self.set_normal_mode();
 
-        let view_current = self.tabs.selected();
-        let view_new = view_current.subgraph()?;
-        self.tabs.open(view_new);
+        let task = self.tabs.selected().subgraph()?;
+
+        self.derive("subgraph".to_string(), task)
+    }
+
+    /// Open the subgraph popup, with the subgraph enclosing the current node selected.
+    pub fn goto_cluster(&mut self) -> DotViewerResult<()> {
+        let id = self.tabs.selected().cluster_id()?;
+
+        self.goto_subgraph(&id)
+    }
+
+    /// Open the subgraph popup, with the subgraph `id` selected.
+    fn goto_subgraph(&mut self, id: &str) -> DotViewerResult<()> {
+        let view = self.tabs.selected();
+
+        // the subgraph may be filtered out
+        if !view.subtree.filter.is_empty() {
+            view.subtree.set_filter(String::new());
+        }
+        if !view.subtree.select_id(id) {
+            return Err(DotViewerError::ViewerError(format!("no such subgraph {id}")));
+        }
+
+        self.set_popup_mode(PopupMode::Tree);
 
         Ok(())
     }
 
+    /// Open the subgraph enclosing the current node as a new tab.
+    pub fn cluster(&mut self) -> DotViewerResult<()> {
+        let task = self.tabs.selected().cluster()?;
+
+        self.derive("cluster".to_string(), task)
+    }
+
+    /// Open the legend popup, summarizing the colors of nodes in the current tab.
+    pub fn show_legend(&mut self) {
+        let mut counts: HashMap<(&str, bool), (&Paint, usize)> = HashMap::new();
+        for paint in self.tabs.selected().paints.values() {
+            let (_, count) = counts.entry((&paint.spec, paint.filled)).or_insert((paint, 0));
+            *count += 1;
+        }
+
+        let mut rows: Vec<(Paint, usize)> =
+            counts.into_values().map(|(paint, count)| (paint.clone(), count)).collect();
+        rows.sort_by(|(a, m), (b, n)| n.cmp(m).then_with(|| a.spec.cmp(&b.spec)));
+
+        self.legend = List::from_iter(rows);
+        self.set_popup_mode(PopupMode::Legend);
+    }
+
+    /// Open the tabs popup, showing the derivation tree of tabs with the current tab selected.
+    pub fn show_lineage(&mut self) {
+        self.update_lineage();
+        self.set_popup_mode(PopupMode::Lineage);
+    }
+
+    fn update_lineage(&mut self) {
+        let links: Vec<(usize, Option<usize>)> = (self.tabs.tabs.iter())
+            .map(|view| (view.id, view.origin.as_ref().map(|origin| origin.parent)))
+            .collect();
+
+        self.lineage = Lineage::new(&links);
+        self.lineage.select(self.tabs.state);
+    }
+
+    /// Switch to the tab selected in tabs popup.
+    pub fn goto_selected_tab(&mut self) -> DotViewerResult<()> {
+        let idx = (self.lineage.selected())
+            .ok_or(DotViewerError::ViewerError("no tab selected".to_string()))?;
+
+        self.set_normal_mode();
+        self.tabs.select(idx);
+
+        Ok(())
+    }
+
+    /// Close the tab selected in tabs popup, along with all tabs derived from it.
+    pub fn close_branch(&mut self) -> DotViewerResult<()> {
+        let branch = self.lineage.branch();
+        if branch.len() == self.tabs.tabs.len() {
+            return Err(DotViewerError::ViewerError("cannot close all tabs".to_string()));
+        }
+
+        // closed from the last, such that reopening restores them from the first
+        for idx in branch {
+            self.tabs.close_at(idx)?;
+        }
+        self.update_lineage();
+
+        Ok(())
+    }
+
+    /// Go back to the parent of the tab selected in tabs popup, ready to derive it again
+    /// with different arguments, i.e.,
+    /// search for a filter, select a subgraph, or edit a command.
+    pub fn rederive(&mut self) -> DotViewerResult<()> {
+        let idx = (self.lineage.selected())
+            .ok_or(DotViewerError::ViewerError("no tab selected".to_string()))?;
+        let origin = (self.tabs.tabs[idx].origin.clone()).ok_or(DotViewerError::ViewerError(
+            "the tab is not derived from another".to_string(),
+        ))?;
+        let parent = (self.tabs.tabs.iter())
+            .position(|view| view.id == origin.parent)
+            .ok_or(DotViewerError::ViewerError("the parent tab is closed".to_string()))?;
+
+        self.tabs.select(parent);
+
+        match origin.derivation {
+            Derivation::Filter { smode, key } => {
+                self.set_search_mode(smode);
+                self.input.set(key);
+                self.update_search();
+            }
+            Derivation::Subgraph { key } => self.goto_subgraph(&key)?,
+            derivation => {
+                if let Derivation::Neighbors { id, .. } = &derivation {
+                    self.tabs.selected().goto(id)?;
+                }
+                self.set_command_mode();
+                self.input.set(derivation.command());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Split the screen, showing the tab at `other` beside the selected one.
+    /// By default, it shows the tab that the selected one was derived from, or the previous tab.
+    pub fn split(&mut self, other: Option<usize>) -> DotViewerResult<()> {
+        let other = match other {
+            Some(other) => other,
+            None => {
+                if self.tabs.tabs.len() == 1 {
+                    let msg = "no other tab to show beside".to_string();
+                    return Err(DotViewerError::ViewerError(msg));
+                }
+
+                let view = &self.tabs.tabs[self.tabs.state];
+                let parent = (view.origin.as_ref()).and_then(|origin| {
+                    self.tabs.tabs.iter().position(|view| view.id == origin.parent)
+                });
+                let previous = (self.tabs.state + self.tabs.tabs.len() - 1) % self.tabs.tabs.len();
+
+                parent.unwrap_or(previous)
+            }
+        };
+
+        self.tabs.split(other)?;
+        self.sync_selection();
+
+        Ok(())
+    }
+
+    /// Split the screen, or restore it if already split.
+    pub fn toggle_split(&mut self) -> DotViewerResult<()> {
+        match self.tabs.split {
+            Some(_) => {
+                self.tabs.split = None;
+                Ok(())
+            }
+            None => self.split(None),
+        }
+    }
+
+    /// Toggle whether selecting a node selects the same node in the other pane.
+    pub fn toggle_sync(&mut self) -> DotViewerResult<()> {
+        let split = (self.tabs.split.as_mut())
+            .ok_or(DotViewerError::ViewerError("the screen is not split".to_string()))?;
+        split.sync = !split.sync;
+
+        self.sync_selection();
+
+        Ok(())
+    }
+
+    /// Select the current node of the selected tab in the other pane, if synced and present.
+    pub fn sync_selection(&mut self) {
+        let other = match &self.tabs.split {
+            Some(split) if split.sync => split.other,
+            _ => return,
+        };
+
+        let id = self.tabs.selected().current_id();
+        let view = &mut self.tabs.tabs[other];
+        if view.current_id() != id {
+            // the node may not be in the other view
+            let _ = view.select(&id);
+        }
+    }
+
     pub fn set_normal_mode(&mut self) {
         self.mode = Mode::Normal;
     }
@@ -275,6 +1043,7 @@ impl App {
 
     pub fn set_search_mode(&mut self, smode: SearchMode) {
         self.input.clear();
+        self.record_jump();
 
         self.mode = Mode::Search(smode);
 
@@ -285,6 +1054,63 @@ impl App {
         view.nexts = List::from_iter(Vec::new());
     }
 
+    /// Copy the `target` to the clipboard, which is the id of the selected node (`id`),
+    /// the selected node in dot (`attrs`), or the current tab in dot (`dot`).
+    pub fn yank(&mut self, target: &str) -> DotViewerResult<Success> {
+        self.set_normal_mode();
+
+        if let Some(job) = &self.yank {
+            return Err(DotViewerError::ViewerError(format!("{} is running", job.name)));
+        }
+
+        let view = self.tabs.selected();
+        let id = view.previewed_id();
+
+        let (text, what) = match target {
+            "id" => (id.clone(), format!("id {id}")),
+            "attrs" => (view.raw(&id).trim().to_string(), format!("node {id}")),
+            "dot" => {
+                let mut buffer = Vec::new();
+                view.graph.to_dot(&mut buffer)?;
+                let text = String::from_utf8(buffer)
+                    .map_err(|e| DotViewerError::ViewerError(e.to_string()))?;
+
+                (text, format!("tab {}", view.title))
+            }
+            _ => return Err(DotViewerError::CommandError(format!("no such yank target {target}"))),
+        };
+
+        let copy = self.clipboard.copy(text)?;
+        let name = format!("yank {what}");
+        let job = Job::spawn(
+            name,
+            Box::new(move |progress| Ok(format!("{what} to {}", copy(progress)?))),
+        );
+
+        match job.wait(GRACE) {
+            Some(yanked) => Ok(Success::YankSuccess(yanked?)),
+            None => {
+                self.yank = Some(job);
+                Ok(Success::default())
+            }
+        }
+    }
+
+    /// Log the result of the last action or command, unless it is silent.
+    pub fn log_result(&mut self) {
+        let (text, error) = match &self.result {
+            Ok(succ) => (succ.to_string(), false),
+            Err(err) => {
+                warn!("{err}");
+                (err.to_string(), true)
+            }
+        };
+
+        if !text.is_empty() {
+            self.messages.push(text, error);
+        }
+    }
+
     pub fn set_popup_mode(&mut self, pmode: PopupMode) {
         self.mode = Mode::Popup(pmode);
     }
@@ -7,8 +7,23 @@ pub(crate) enum Command {
     Export(Export),
     Xdot(Xdot),
     Filter,
+    Where(Where),
     Help,
     Subgraph,
+    Marks,
+    Tabs,
+    Legend,
+    Messages,
+    Yank(Yank),
+    Split(Split),
+    Sketch(Sketch),
+    Template(TemplateArg),
+    Rename(Rename),
+    Move(Move),
+    Duplicate,
+    Close,
+    Only,
+    Reopen,
     Quit,
     NoMatch,
 }
@@ -28,20 +43,77 @@ pub(crate) struct Xdot {
     pub(crate) filename: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Sketch {
+    pub(crate) depth: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Yank {
+    pub(crate) target: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Where {
+    pub(crate) field: Option<String>,
+    pub(crate) op: Option<String>,
+    pub(crate) value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct TemplateArg {
+    pub(crate) template: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Rename {
+    pub(crate) title: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Move {
+    pub(crate) position: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Split {
+    pub(crate) position: Option<usize>,
+}
+
 pub(crate) struct CommandTrie {
     pub(crate) trie_cmd: Trie,
     pub(crate) _trie_arg: Trie,
 }
 
-fn subcommands() -> [ClapCommand; 7] {
+fn subcommands() -> [ClapCommand; 22] {
     [
         ClapCommand::new("neighbors")
             .arg(Arg::new("depth").value_parser(clap::value_parser!(usize))),
         ClapCommand::new("export").arg(Arg::new("filename")),
         ClapCommand::new("xdot").arg(Arg::new("filename")),
         ClapCommand::new("filter"),
+        ClapCommand::new("where")
+            .arg(Arg::new("field"))
+            .arg(Arg::new("op").allow_hyphen_values(true))
+            .arg(Arg::new("value").allow_hyphen_values(true)),
         ClapCommand::new("help"),
         ClapCommand::new("subgraph"),
+        ClapCommand::new("marks"),
+        ClapCommand::new("tabs"),
+        ClapCommand::new("legend"),
+        ClapCommand::new("messages"),
+        ClapCommand::new("yank").arg(Arg::new("target").value_parser(["id", "attrs", "dot"])),
+        ClapCommand::new("split")
+            .arg(Arg::new("position").value_parser(clap::value_parser!(usize))),
+        ClapCommand::new("sketch").arg(Arg::new("depth").value_parser(clap::value_parser!(usize))),
+        ClapCommand::new("template")
+            .arg(Arg::new("template").num_args(1..).allow_hyphen_values(true)),
+        ClapCommand::new("rename").arg(Arg::new("title").num_args(1..).allow_hyphen_values(true)),
+        ClapCommand::new("move").arg(Arg::new("position").value_parser(clap::value_parser!(usize))),
+        ClapCommand::new("dup"),
+        ClapCommand::new("close"),
+        ClapCommand::new("only"),
+        ClapCommand::new("reopen"),
         ClapCommand::new("q"),
     ]
 }
@@ -79,8 +151,62 @@ impl Command {
                     Self::Xdot(xdot)
                 }
                 Some(("filter", _)) => Self::Filter,
+                Some(("where", matches)) => {
+                    let field = matches.get_one::<String>("field").cloned();
+                    let op = matches.get_one::<String>("op").cloned();
+                    let value = matches.get_one::<String>("value").cloned();
+                    let where_ = Where { field, op, value };
+
+                    Self::Where(where_)
+                }
                 Some(("help", _)) => Self::Help,
                 Some(("subgraph", _)) => Self::Subgraph,
+                Some(("marks", _)) => Self::Marks,
+                Some(("tabs", _)) => Self::Tabs,
+                Some(("legend", _)) => Self::Legend,
+                Some(("messages", _)) => Self::Messages,
+                Some(("yank", matches)) => {
+                    let target = matches.get_one::<String>("target").cloned();
+                    let yank = Yank { target };
+
+                    Self::Yank(yank)
+                }
+                Some(("split", matches)) => {
+                    let position = matches.get_one::<usize>("position").copied();
+                    let split = Split { position };
+
+                    Self::Split(split)
+                }
+                Some(("sketch", matches)) => {
+                    let depth = matches.get_one::<usize>("depth").copied();
+                    let sketch = Sketch { depth };
+
+                    Self::Sketch(sketch)
+                }
+                Some(("template", matches)) => {
+                    let template = (matches.get_many::<String>("template"))
+                        .map(|words| words.cloned().collect::<Vec<_>>().join(" "));
+                    let template = TemplateArg { template };
+
+                    Self::Template(template)
+                }
+                Some(("rename", matches)) => {
+                    let title = (matches.get_many::<String>("title"))
+                        .map(|words| words.cloned().collect::<Vec<_>>().join(" "));
+                    let rename = Rename { title };
+
+                    Self::Rename(rename)
+                }
+                Some(("move", matches)) => {
+                    let position = matches.get_one::<usize>("position").copied();
+                    let move_ = Move { position };
+
+                    Self::Move(move_)
+                }
+                Some(("dup", _)) => Self::Duplicate,
+                Some(("close", _)) => Self::Close,
+                Some(("only", _)) => Self::Only,
+                Some(("reopen", _)) => Self::Reopen,
                 Some(("q", _)) => Self::Quit,
                 _ => unreachable!(),
             },
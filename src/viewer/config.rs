@@ -0,0 +1,93 @@
+use crate::viewer::error::{DotViewerError, DotViewerResult};
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// `Config` holds user configurations of `dot-viewer`,
+/// read from `$XDG_CONFIG_HOME/dot-viewer/config.toml` (or `~/.config/dot-viewer/config.toml`).
+///
+/// Every entry is optional, and missing entries fall back to the defaults.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct Config {
+    /// Named numeric fields to extract from nodes, in `name = "regex"` mappings,
+    /// where the first capture group of the regex holds the value
+    pub fields: HashMap<String, String>,
+
+    /// Keybindings in `[keys.<context>]` tables of `"keys" = "action"` mappings,
+    /// overriding the default keybindings
+    pub keys: HashMap<String, HashMap<String, String>>,
+
+    /// Theme of the user interface
+    pub theme: ThemeConfig,
+
+    /// Template rendering the items of node lists from node attributes, e.g., `{label:first_line}`
+    pub template: Option<String>,
+
+    /// How yanked texts are copied to the clipboard
+    pub clipboard: ClipboardConfig,
+}
+
+/// `ThemeConfig` selects a built-in theme and overrides its styles.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct ThemeConfig {
+    /// Name of the built-in theme, `dark` by default
+    pub name: Option<String>,
+    /// Styles in `name = "style"` mappings, e.g., `prev = "red on black bold"`
+    pub styles: HashMap<String, String>,
+}
+
+/// `ClipboardConfig` selects how yanked texts are copied to the clipboard.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct ClipboardConfig {
+    /// Whether to copy by OSC 52 escape sequences written to the terminal, `true` by default
+    pub osc52: bool,
+    /// Command reading texts from its stdin, e.g., `xclip -selection clipboard`, if OSC 52 is not used
+    pub command: Option<String>,
+    /// File to write texts to, if neither OSC 52 nor a command is used
+    pub file: Option<String>,
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self { osc52: true, command: None, file: None }
+    }
+}
+
+impl Config {
+    /// Load the config from the given `path`, or from the default path if not given.
+    /// Returns the default config if there is no config file in the default path.
+    pub fn load(path: Option<&str>) -> DotViewerResult<Self> {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => match default_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Self::default()),
+            },
+        };
+
+        let contents = fs::read_to_string(&path)?;
+
+        toml::from_str(&contents)
+            .map_err(|e| DotViewerError::ConfigError(format!("{}: {e}", path.display())))
+    }
+}
+
+/// Directory holding `dot-viewer` configurations.
+pub(crate) fn config_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(base.join("dot-viewer"))
+}
+
+fn default_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.toml"))
+}
@@ -20,4 +20,6 @@ pub enum DotViewerError {
     IOError(#[from] std::io::Error),
     #[error("Err: failed to launch xdot.py")]
     XdotError,
+    #[error("Err: invalid config, `{0}`")]
+    ConfigError(String),
 }
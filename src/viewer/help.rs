@@ -1,33 +1,188 @@
+use crate::viewer::keymap::{Action, Context, Keymap};
+
 pub(super) const HEADER: &[&str] = &["When", "Key", "Command", "Actions"];
 
-pub(super) const ROWS: &[&[&str]] = &[
-    &["Quit", "q", "", "quit dot-viewer"],
-    &["Help", "", ":help<CR>", "help"],
-    &["", "", "", ""],
-    &["All", "esc", "", "go back to Normal mode"],
-    &["Normal", "/", "", "go to fuzzy search mode"],
-    &["Normal", "r", "", "go to regex search mode"],
-    &["Normal", ":", "", "go to command mode"],
-    &["", "", "", ""],
-    &["Normal", "c", "", "close the current tab (view)"],
-    &["", "h/l", "", "move focus between current, prevs, nexts list"],
-    &["", "j/k", "", "traverse in focused list"],
-    &["", "n/N", "", "go to next/previous match"],
-    &["", "tab/backtab", "", "move between tabs"],
-    &["Search", "tab", "", "autocomplete search keyword"],
-    &["", "enter", "", "apply search"],
-    &["Command", "", "filter", "apply filter on current matches"],
-    &["", "", "neighbors [depth]", "get up to [depth] neighbors of the current node"],
-    &["", "", "export [(opt) filename]", "export the current tab (view) to dot"],
-    &[
-        "",
-        "",
-        "xdot [(opt) filename]",
-        "launch xdot, showing the most current exported file on default",
-    ],
-    &["", "", "subgraph", "go to subgraph Popup mode"],
-    &["", "tab", "", "autocomplete command"],
-    &["", "enter", "", "execute command"],
-    &["Subgraph Popup", "h/j/k/l", "", "traverse tree"],
-    &["", "enter", "", "change root to the selected subgraph"],
+/// Helps that do not come from the keymap, shown after the keybindings of each context.
+const EXTRAS: &[(Context, &[&str])] = &[
+    (Context::Normal, &["", "[count]", "", "prefix motions with a count, e.g., 5j or 100G"]),
+    (Context::Normal, &["", "click", "", "select in a list, focus the attrs, or switch tabs"]),
+    (Context::Normal, &["", "double click", "", "go to the clicked prev/next node"]),
+    (Context::Normal, &["", "wheel", "", "scroll the list or the attrs under the cursor"]),
+    (Context::Command, &["", "", "help", "show help"]),
+    (Context::Command, &["", "", "q", "quit dot-viewer"]),
+    (Context::Command, &["", "", "filter", "apply filter on current matches"]),
+    (
+        Context::Command,
+        &[
+            "",
+            "",
+            "where [field] [op] [value]",
+            "apply filter on a numeric field, e.g., where D > 64",
+        ],
+    ),
+    (
+        Context::Command,
+        &["", "", "neighbors [depth]", "get up to [depth] neighbors of the current node"],
+    ),
+    (
+        Context::Command,
+        &["", "", "export [(opt) filename]", "export the current tab (view) to dot"],
+    ),
+    (
+        Context::Command,
+        &[
+            "",
+            "",
+            "xdot [(opt) filename]",
+            "launch xdot, showing the most current exported file on default",
+        ],
+    ),
+    (Context::Command, &["", "", "subgraph", "go to subgraph Popup mode"]),
+    (Context::Command, &["", "", "marks", "go to marks Popup mode"]),
+    (Context::Command, &["", "", "tabs", "go to tabs Popup mode"]),
+    (Context::Command, &["", "", "legend", "go to legend Popup mode"]),
+    (Context::Command, &["", "", "messages", "go to messages Popup mode"]),
+    (
+        Context::Command,
+        &["", "", "yank [id|attrs|dot]", "copy the node id, the node or the tab in dot"],
+    ),
+    (
+        Context::Command,
+        &[
+            "",
+            "",
+            "split [(opt) position]",
+            "show the tab (view) at position beside the current one",
+        ],
+    ),
+    (
+        Context::Command,
+        &[
+            "",
+            "",
+            "sketch [(opt) depth]",
+            "sketch the neighborhood of the current node, up to depth 2 on default",
+        ],
+    ),
+    (
+        Context::Command,
+        &["", "", "template [template]", "show nodes rendered from the template, e.g., {label}"],
+    ),
+    (Context::Command, &["", "", "rename [title]", "rename the current tab (view)"]),
+    (
+        Context::Command,
+        &["", "", "move [position]", "move the current tab (view) to position, from 1"],
+    ),
+    (Context::Command, &["", "", "dup", "duplicate the current tab (view)"]),
+    (Context::Command, &["", "", "close", "close the current tab (view)"]),
+    (Context::Command, &["", "", "only", "close all tabs (views) but the current one"]),
+    (Context::Command, &["", "", "reopen", "reopen the latest closed tab (view)"]),
+    (Context::Popup, &["", "[count]", "", "prefix motions with a count"]),
+    (Context::Popup, &["", "click/wheel", "", "select/scroll in popups"]),
+    (Context::Popup, &["", "double click", "", "act as enter, or toggle a subgraph in the tree"]),
 ];
+
+/// Rows of help, generated from the active `keymap` such that they are never out of date.
+pub(super) fn rows(keymap: &Keymap) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+
+    for context in Context::all() {
+        let mut section: Vec<Vec<String>> = Action::all()
+            .filter_map(|action| {
+                let keys = keys(keymap, context, action);
+                (!keys.is_empty()).then(|| {
+                    vec![String::new(), keys, String::new(), action.description().to_string()]
+                })
+            })
+            .collect();
+
+        let extras = EXTRAS.iter().filter(|(c, _)| *c == context);
+        section.extend(extras.map(|(_, row)| row.iter().map(|s| s.to_string()).collect()));
+
+        if let Some(row) = section.first_mut() {
+            row[0] = context.title().to_string();
+
+            if !rows.is_empty() {
+                rows.push(vec![String::new(); HEADER.len()]);
+            }
+            rows.append(&mut section);
+        }
+    }
+
+    rows
+}
+
+/// Keys bound to `action` in `context`, e.g., `k, up`.
+fn keys(keymap: &Keymap, context: Context, action: Action) -> String {
+    let keys: Vec<String> = (keymap.bindings(context).iter())
+        .filter(|(_, bound)| *bound == action)
+        .map(|(chords, _)| {
+            let keys: String = chords.iter().map(|chord| chord.to_string()).collect();
+            if action.takes_char() {
+                format!("{keys}[a-z]")
+            } else {
+                keys
+            }
+        })
+        .collect();
+
+    keys.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    fn row<'a>(rows: &'a [Vec<String>], description: &str) -> &'a [String] {
+        rows.iter().find(|row| row[3] == description).unwrap()
+    }
+
+    #[test]
+    fn generate_sections() {
+        let rows = rows(&Keymap::new(&HashMap::new()).unwrap());
+
+        assert!(rows.iter().all(|row| row.len() == HEADER.len()));
+        assert_eq!(rows[0][0], Context::Normal.title());
+
+        let titles: Vec<&str> =
+            rows.iter().map(|row| row[0].as_str()).filter(|t| !t.is_empty()).collect();
+        // contexts without bindings nor extras, e.g., help, have no section
+        let expected: Vec<&str> = (Context::all())
+            .filter(|context| context != &Context::Help)
+            .map(|context| context.title())
+            .collect();
+        assert_eq!(titles, expected);
+
+        // sections are separated by a blank row
+        for (idx, row) in rows.iter().enumerate().skip(1) {
+            if !row[0].is_empty() {
+                assert!(rows[idx - 1].iter().all(String::is_empty));
+            }
+        }
+    }
+
+    #[test]
+    fn show_bound_keys() {
+        let rows = rows(&Keymap::new(&HashMap::new()).unwrap());
+
+        assert_eq!(row(&rows, Action::Down.description())[1], "j, down");
+        assert_eq!(row(&rows, Action::Mark.description())[1], "m[a-z]");
+        assert_eq!(row(&rows, Action::GotoMark.description())[1], "'[a-z]");
+    }
+
+    #[test]
+    fn show_overridden_keys() {
+        let bindings = HashMap::from([
+            ("m".to_string(), "none".to_string()),
+            ("M".to_string(), "mark".to_string()),
+            ("q".to_string(), "none".to_string()),
+        ]);
+        let keymap = Keymap::new(&HashMap::from([("normal".to_string(), bindings)])).unwrap();
+        let rows = rows(&keymap);
+
+        assert_eq!(row(&rows, Action::Mark.description())[1], "M[a-z]");
+        assert!(rows.iter().all(|row| row[3] != Action::Quit.description() || row[1].is_empty()));
+    }
+}
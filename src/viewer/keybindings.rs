@@ -1,232 +1,299 @@
 use crate::viewer::{
     app::App,
     error::{DotViewerError, DotViewerResult},
+    keymap::{Action, Chord, Context, Lookup},
     modes::{Mode, PopupMode, SearchMode},
+    panes::Panes,
     success::Success,
     view::{Focus, View},
 };
 
-use crossterm::event::{KeyCode, KeyEvent};
-use log::{info, warn};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use log::info;
 
 impl App {
     pub fn key(&mut self, key: KeyEvent) {
         info!("{:?}", key.code);
 
-        self.result = match key.code {
-            KeyCode::Char(c) => self.char(c).map(|_| Success::default()),
-            KeyCode::Enter => self.enter(),
-            KeyCode::Backspace => self.backspace().map(|_| Success::default()),
-            KeyCode::Esc => self.esc().map(|_| Success::default()),
-            KeyCode::Tab => self.tab().map(|_| Success::default()),
-            KeyCode::BackTab => self.backtab().map(|_| Success::default()),
-            KeyCode::Up => self.up().map(|_| Success::default()),
-            KeyCode::Down => self.down().map(|_| Success::default()),
-            KeyCode::Right => self.right().map(|_| Success::default()),
-            KeyCode::Left => self.left().map(|_| Success::default()),
-            _ => Ok(Success::default()),
-        };
+        self.result = self.chord(key);
+        self.sync_selection();
+        self.log_result();
+    }
+
+    fn chord(&mut self, key: KeyEvent) -> DotViewerResult<Success> {
+        let chord = Chord::from(key);
+
+        // the next character is the argument of a pending action, e.g., `a` of `ma`
+        if let Some(action) = self.pending.take() {
+            self.count = None;
 
-        if let Err(err) = &self.result {
-            warn!("{err}");
+            return match chord {
+                Chord { code: KeyCode::Char(c), modifiers: KeyModifiers::NONE } => {
+                    self.act_char(action, c).map(|_| Success::default())
+                }
+                _ => Err(DotViewerError::KeyError(key.code)),
+            };
         }
 
-        self.lookback = Some(key.code);
-    }
+        if self.keys.is_empty() && self.digit(chord) {
+            return Ok(Success::default());
+        }
 
-    fn char(&mut self, c: char) -> DotViewerResult<()> {
-        match &self.mode {
-            Mode::Normal => self.char_normal(c)?,
-            Mode::Command => self.char_command(c)?,
-            Mode::Search(_) => self.char_search(c),
-            Mode::Popup(_) => self.char_popup(c)?,
-        };
+        // keep typing while the keys are a prefix of some bindings, e.g., `g` of `gg`
+        self.keys.push(chord);
+        let lookup = self.keymap.lookup(Context::of(&self.mode), &self.keys);
+        if lookup == Lookup::Prefix {
+            return Ok(Success::default());
+        }
+        let keys = std::mem::take(&mut self.keys);
 
-        Ok(())
-    }
+        if let Lookup::Action(action) = lookup {
+            if action.takes_char() {
+                self.pending = Some(action);
+                return Ok(Success::default());
+            }
+        }
 
-    fn char_normal(&mut self, c: char) -> DotViewerResult<()> {
-        match c {
-            '/' => self.set_search_mode(SearchMode::Fuzzy),
-            'r' => self.set_search_mode(SearchMode::Regex),
-            ':' => self.set_command_mode(),
-            'c' => self.tabs.close()?,
-            'h' => self.left()?,
-            'j' => self.down()?,
-            'k' => self.up()?,
-            'l' => self.right()?,
-            'n' => self.goto_next_match()?,
-            'N' => self.goto_prev_match()?,
-            'g' => self.goto_first()?,
-            'G' => self.goto_last()?,
-            _ => Err(DotViewerError::KeyError(KeyCode::Char(c)))?,
+        let res = match lookup {
+            Lookup::Action(action) => self.act(action),
+            _ => self.insert(&keys).map(|_| Success::default()),
         };
 
-        Ok(())
-    }
-
-    fn char_command(&mut self, c: char) -> DotViewerResult<()> {
-        self.input.insert(c);
-        Ok(())
-    }
+        self.count = None;
 
-    fn char_search(&mut self, c: char) {
-        self.input.insert(c);
-        self.update_search();
+        res
     }
 
-    fn char_popup(&mut self, c: char) -> DotViewerResult<()> {
-        match &self.mode {
-            Mode::Popup(pmode) => match pmode {
-                PopupMode::Tree => self.char_tree(c),
-                PopupMode::Help => self.char_help(c),
-            },
-            _ => unreachable!(),
+    /// Append a digit to the count prefix in Normal mode and popups,
+    /// returning whether `chord` was such a digit.
+    fn digit(&mut self, chord: Chord) -> bool {
+        match (&self.mode, chord) {
+            (Mode::Popup(PopupMode::TreeFilter), _) => false,
+            (Mode::Normal | Mode::Popup(_), Chord { code: KeyCode::Char(c), modifiers })
+                if modifiers == KeyModifiers::NONE
+                    && c.is_ascii_digit()
+                    && (c != '0' || self.count.is_some()) =>
+            {
+                self.push_count(c.to_digit(10).unwrap());
+                true
+            }
+            _ => false,
         }
     }
 
-    fn char_tree(&mut self, c: char) -> DotViewerResult<()> {
-        match c {
-            'h' => self.left(),
-            'j' => self.down(),
-            'k' => self.up(),
-            'l' => self.right(),
-            _ => Err(DotViewerError::KeyError(KeyCode::Char(c))),
+    /// Insert unbound characters to the input form in Command and Search mode,
+    /// or to the filter of the subgraph popup.
+    fn insert(&mut self, keys: &[Chord]) -> DotViewerResult<()> {
+        let chars: Option<Vec<char>> = (keys.iter())
+            .map(|chord| match chord {
+                Chord { code: KeyCode::Char(c), modifiers }
+                    if !modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    Some(*c)
+                }
+                _ => None,
+            })
+            .collect();
+
+        match (&self.mode, chars) {
+            (Mode::Command, Some(chars)) => chars.into_iter().for_each(|c| self.input.insert(c)),
+            (Mode::Search(_), Some(chars)) => {
+                chars.into_iter().for_each(|c| self.input.insert(c));
+                self.update_search();
+            }
+            (Mode::Popup(PopupMode::TreeFilter), Some(chars)) => {
+                let subtree = &mut self.tabs.selected().subtree;
+                let mut filter = subtree.filter.clone();
+                filter.extend(chars);
+                subtree.set_filter(filter);
+            }
+            _ => {
+                let code = keys.last().map_or(KeyCode::Null, |chord| chord.code);
+                Err(DotViewerError::KeyError(code))?
+            }
         }
-    }
 
-    fn char_help(&mut self, c: char) -> DotViewerResult<()> {
-        match c {
-            'j' => self.down(),
-            'k' => self.up(),
-            _ => Err(DotViewerError::KeyError(KeyCode::Char(c))),
-        }
+        Ok(())
     }
 
-    fn enter(&mut self) -> DotViewerResult<Success> {
-        match &self.mode {
-            Mode::Normal => {
-                let view = self.tabs.selected();
-                view.enter().map(|_| Success::default())
+    /// Perform `action`, bound to the typed keys.
+    pub fn act(&mut self, action: Action) -> DotViewerResult<Success> {
+        match action {
+            Action::Quit => self.quit = true,
+            Action::Cancel => return Ok(self.cancel()),
+            Action::SearchFuzzy => self.set_search_mode(SearchMode::Fuzzy),
+            Action::SearchRegex => self.set_search_mode(SearchMode::Regex),
+            Action::CommandMode => self.set_command_mode(),
+            Action::CloseTab => self.tabs.close()?,
+            Action::ReopenTab => self.tabs.reopen()?,
+            Action::ShowTabs => self.show_lineage(),
+            Action::SplitScreen => self.toggle_split()?,
+            Action::SwitchPane => self.tabs.switch()?,
+            Action::SyncSelection => self.toggle_sync()?,
+            Action::NextTab => self.tabs.next(),
+            Action::PrevTab => self.tabs.previous(),
+            Action::FocusLeft => self.tabs.selected().left(&self.panes),
+            Action::FocusRight => self.tabs.selected().right(&self.panes),
+            Action::Up => self.up()?,
+            Action::Down => self.down()?,
+            Action::First => self.goto_first()?,
+            Action::Last => self.goto_last()?,
+            Action::Percent => self.goto_percent()?,
+            Action::HalfPageDown => self.scroll(0.5)?,
+            Action::HalfPageUp => self.scroll(-0.5)?,
+            Action::PageDown => self.scroll(1.0)?,
+            Action::PageUp => self.scroll(-1.0)?,
+            Action::GotoAdjacent => self.tabs.selected().enter()?,
+            Action::NextMatch => self.goto_next_match()?,
+            Action::PrevMatch => self.goto_prev_match()?,
+            Action::ChainNext => self.goto_chain(true)?,
+            Action::ChainPrev => self.goto_chain(false)?,
+            Action::NearestSink => self.goto_terminal(true)?,
+            Action::NearestSource => self.goto_terminal(false)?,
+            Action::JumpBack => self.goto_jump_back()?,
+            Action::JumpForward => self.goto_jump_forward()?,
+            Action::Mark | Action::GotoMark => {
+                let msg = format!("{action} takes a character argument");
+                Err(DotViewerError::ViewerError(msg))?
             }
-            Mode::Command => self.exec(),
-            Mode::Search(_) => {
+            Action::GrowNodes => self.relayout(|panes| panes.resize_nodes(true)),
+            Action::ShrinkNodes => self.relayout(|panes| panes.resize_nodes(false)),
+            Action::GrowAdjacent => self.relayout(|panes| panes.resize_adjacent(true)),
+            Action::ShrinkAdjacent => self.relayout(|panes| panes.resize_adjacent(false)),
+            Action::FlipLayout => self.relayout(Panes::flip),
+            Action::ResetLayout => self.relayout(|panes| *panes = Panes::default()),
+            Action::Maximize => self.panes.maximized = !self.panes.maximized,
+            Action::ToggleAdjacent => {
+                self.relayout(|panes| panes.show_adjacent = !panes.show_adjacent)
+            }
+            Action::ToggleAttrs => self.relayout(|panes| panes.show_attrs = !panes.show_attrs),
+            Action::GotoCluster => self.goto_cluster()?,
+            Action::OpenCluster => self.cluster()?,
+            Action::CycleAttrs => {
+                self.tabs.selected().attrs.nth(0);
+                self.relayout(Panes::cycle_attrs)
+            }
+            Action::ToggleLabels => self.toggle_labels(),
+            Action::ScrollLeft => {
+                let count = self.take_count();
+                self.tabs.selected().shift(false, count)
+            }
+            Action::ScrollRight => {
+                let count = self.take_count();
+                self.tabs.selected().shift(true, count)
+            }
+            Action::ToggleElide => self.relayout(|panes| panes.elided = !panes.elided),
+            Action::ToggleMinimap => self.relayout(|panes| panes.minimap = !panes.minimap),
+            Action::ShowLegend => self.show_legend(),
+            Action::ShowMessages => self.set_popup_mode(PopupMode::Messages),
+            Action::YankId => return self.yank("id"),
+            Action::YankAttrs => return self.yank("attrs"),
+            Action::YankDot => return self.yank("dot"),
+            Action::Complete => self.complete()?,
+            Action::Execute => return self.exec(),
+            Action::ApplySearch => {
+                self.settle_search();
                 self.set_normal_mode();
-                Ok(Success::default())
+                self.record_jump();
             }
-            Mode::Popup(pmode) => match pmode {
-                PopupMode::Tree => self.subgraph().map(|_| Success::default()),
-                _ => Ok(Success::default()),
-            },
-        }
-    }
-
-    fn backspace(&mut self) -> DotViewerResult<()> {
-        match &self.mode {
-            Mode::Command => self.input.delete(),
-            Mode::Search(_) => {
-                self.input.delete();
-                self.update_search();
+            Action::DeleteChar => self.backspace()?,
+            Action::CursorLeft => self.input.back(),
+            Action::CursorRight => self.input.front(),
+            Action::Fold => self.tabs.selected().subtree.left(),
+            Action::Unfold => self.tabs.selected().subtree.right(),
+            Action::FilterSubgraphs => self.set_popup_mode(PopupMode::TreeFilter),
+            Action::SortSubgraphs => self.tabs.selected().subtree.toggle_sort(),
+            Action::ExpandAll => self.tabs.selected().subtree.expand_all(),
+            Action::CollapseAll => self.tabs.selected().subtree.collapse_all(),
+            Action::ApplyFilter => self.set_popup_mode(PopupMode::Tree),
+            Action::ClearFilter => {
+                self.tabs.selected().subtree.set_filter(String::new());
+                self.set_popup_mode(PopupMode::Tree);
             }
-            _ => Err(DotViewerError::KeyError(KeyCode::Backspace))?,
+            Action::OpenSubgraph => self.subgraph()?,
+            Action::DeleteMark => self.marks.delete()?,
+            Action::GotoSelectedMark => self.goto_selected_mark()?,
+            Action::GotoSelectedTab => self.goto_selected_tab()?,
+            Action::RederiveTab => self.rederive()?,
+            Action::CloseBranch => self.close_branch()?,
+            Action::SelectLeft => self.sketch_mut()?.left(),
+            Action::SelectRight => self.sketch_mut()?.right(),
+            Action::SketchDeeper => self.resketch(1)?,
+            Action::SketchShallower => self.resketch(-1)?,
+            Action::GotoSketched => self.goto_sketched()?,
         };
 
-        Ok(())
+        Ok(Success::default())
     }
 
-    fn esc(&mut self) -> DotViewerResult<()> {
-        match &self.mode {
-            Mode::Normal => Err(DotViewerError::KeyError(KeyCode::Esc)),
-            _ => {
-                self.set_normal_mode();
-                Ok(())
-            }
+    /// Perform `action` taking the character `c` as its argument.
+    fn act_char(&mut self, action: Action, c: char) -> DotViewerResult<()> {
+        match (action, c) {
+            (Action::Mark, c) => self.set_mark(c),
+            // `''` goes to the node before the latest jump, as Vim's `'` mark
+            (Action::GotoMark, '\'') => self.goto_jump_last(),
+            (Action::GotoMark, c) => self.goto_mark(c),
+            _ => Err(DotViewerError::KeyError(KeyCode::Char(c))),
         }
     }
 
-    fn tab(&mut self) -> DotViewerResult<()> {
+    fn complete(&mut self) -> DotViewerResult<()> {
         match &self.mode {
-            Mode::Normal => self.tabs.next(),
             Mode::Command => self.autocomplete_command(),
-            Mode::Search(smode) => match smode {
-                SearchMode::Fuzzy => self.autocomplete_fuzzy(),
-                SearchMode::Regex => self.autocomplete_regex(),
-            },
+            Mode::Search(_) => self.autocomplete_search(),
             _ => Err(DotViewerError::KeyError(KeyCode::Tab))?,
         };
 
         Ok(())
     }
 
-    fn backtab(&mut self) -> DotViewerResult<()> {
+    fn backspace(&mut self) -> DotViewerResult<()> {
         match &self.mode {
-            Mode::Normal => {
-                self.tabs.previous();
-                Ok(())
+            Mode::Command => self.input.delete(),
+            Mode::Search(_) => {
+                self.input.delete();
+                self.update_search();
             }
-            _ => Err(DotViewerError::KeyError(KeyCode::BackTab)),
-        }
-    }
-
-    fn up(&mut self) -> DotViewerResult<()> {
-        let view = self.tabs.selected();
-
-        match &self.mode {
-            Mode::Normal => view.up()?,
-            Mode::Popup(pmode) => match pmode {
-                PopupMode::Tree => view.subtree.up(),
-                PopupMode::Help => self.help.previous(),
-            },
-            _ => Err(DotViewerError::KeyError(KeyCode::Up))?,
+            Mode::Popup(PopupMode::TreeFilter) => {
+                let subtree = &mut self.tabs.selected().subtree;
+                let mut filter = subtree.filter.clone();
+                filter.pop();
+                subtree.set_filter(filter);
+            }
+            _ => Err(DotViewerError::KeyError(KeyCode::Backspace))?,
         };
 
         Ok(())
     }
 
-    fn down(&mut self) -> DotViewerResult<()> {
-        let view = self.tabs.selected();
-
-        match &self.mode {
-            Mode::Normal => view.down()?,
-            Mode::Popup(pmode) => match pmode {
-                PopupMode::Tree => view.subtree.down(),
-                PopupMode::Help => self.help.next(),
-            },
-            _ => Err(DotViewerError::KeyError(KeyCode::Down))?,
-        };
+    fn up(&mut self) -> DotViewerResult<()> {
+        let n = self.take_count();
 
-        Ok(())
+        self.step(-(n as isize), KeyCode::Up)
     }
 
-    fn right(&mut self) -> DotViewerResult<()> {
-        match &self.mode {
-            Mode::Normal => {
-                let view = self.tabs.selected();
-                view.right()
-            }
-            Mode::Search(_) => self.input.front(),
-            Mode::Popup(PopupMode::Tree) => {
-                let view = self.tabs.selected();
-                view.subtree.right()
-            }
-            _ => Err(DotViewerError::KeyError(KeyCode::Right))?,
-        };
+    fn down(&mut self) -> DotViewerResult<()> {
+        let n = self.take_count();
 
-        Ok(())
+        self.step(n as isize, KeyCode::Down)
     }
 
-    fn left(&mut self) -> DotViewerResult<()> {
+    /// Move the selection of the focused list or popup by `offset`, bound to `code`.
+    fn step(&mut self, offset: isize, code: KeyCode) -> DotViewerResult<()> {
+        let view = self.tabs.selected();
+
         match &self.mode {
-            Mode::Normal => {
-                let view = self.tabs.selected();
-                view.left()
-            }
-            Mode::Search(_) => self.input.back(),
-            Mode::Popup(PopupMode::Tree) => {
-                let view = self.tabs.selected();
-                view.subtree.left()
-            }
-            _ => Err(DotViewerError::KeyError(KeyCode::Left))?,
+            Mode::Normal => view.step(offset)?,
+            Mode::Popup(pmode) => match pmode {
+                PopupMode::Tree | PopupMode::TreeFilter => view.subtree.scroll(offset),
+                PopupMode::Marks => self.marks.list.step(offset),
+                PopupMode::Lineage => self.lineage.list.step(offset),
+                PopupMode::Legend => self.legend.step(offset),
+                PopupMode::Messages => self.messages.list.step(offset),
+                PopupMode::Sketch => self.sketch_mut()?.scroll(offset),
+                PopupMode::Help => self.help.step(offset),
+            },
+            _ => Err(DotViewerError::KeyError(code))?,
         };
 
         Ok(())
@@ -237,49 +304,55 @@ impl View {
     pub fn enter(&mut self) -> DotViewerResult<()> {
         match &self.focus {
             Focus::Prev | Focus::Next => self.goto_adjacent(),
-            Focus::Current => Ok(()),
+            Focus::Current | Focus::Attrs => Ok(()),
         }
     }
 
-    pub fn up(&mut self) -> DotViewerResult<()> {
+    /// Move the selection of the focused list by `offset`,
+    /// wrapping around for a single step, but stopping at the ends for counts.
+    pub fn step(&mut self, offset: isize) -> DotViewerResult<()> {
         match &self.focus {
             Focus::Current => {
-                self.current.previous();
+                self.current.step(offset);
                 self.update_adjacent()?
             }
-            Focus::Prev => self.prevs.previous(),
-            Focus::Next => self.nexts.previous(),
+            Focus::Prev => self.prevs.step(offset),
+            Focus::Next => self.nexts.step(offset),
+            Focus::Attrs => self.attrs.scroll(offset),
         }
 
         Ok(())
     }
 
-    pub fn down(&mut self) -> DotViewerResult<()> {
-        match &self.focus {
-            Focus::Current => {
-                self.current.next();
-                self.update_adjacent()?
+    /// Focus the next shown pane.
+    pub fn right(&mut self, panes: &Panes) {
+        loop {
+            self.focus = match &self.focus {
+                Focus::Current => Focus::Prev,
+                Focus::Prev => Focus::Next,
+                Focus::Next => Focus::Attrs,
+                Focus::Attrs => Focus::Current,
+            };
+
+            if panes.shows(&self.focus) {
+                break;
             }
-            Focus::Prev => self.prevs.next(),
-            Focus::Next => self.nexts.next(),
         }
-
-        Ok(())
     }
 
-    pub fn right(&mut self) {
-        self.focus = match &self.focus {
-            Focus::Current => Focus::Prev,
-            Focus::Prev => Focus::Next,
-            Focus::Next => Focus::Current,
-        };
-    }
-
-    pub fn left(&mut self) {
-        self.focus = match &self.focus {
-            Focus::Current => Focus::Next,
-            Focus::Prev => Focus::Current,
-            Focus::Next => Focus::Prev,
-        };
+    /// Focus the previous shown pane.
+    pub fn left(&mut self, panes: &Panes) {
+        loop {
+            self.focus = match &self.focus {
+                Focus::Current => Focus::Attrs,
+                Focus::Prev => Focus::Current,
+                Focus::Next => Focus::Prev,
+                Focus::Attrs => Focus::Next,
+            };
+
+            if panes.shows(&self.focus) {
+                break;
+            }
+        }
     }
 }
@@ -0,0 +1,685 @@
+use crate::viewer::{
+    error::{DotViewerError, DotViewerResult},
+    modes::{Mode, PopupMode},
+};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// `Action` is a named operation that keys are bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Action {
+    Quit,
+    Cancel,
+    SearchFuzzy,
+    SearchRegex,
+    CommandMode,
+    CloseTab,
+    ReopenTab,
+    ShowTabs,
+    SplitScreen,
+    SwitchPane,
+    SyncSelection,
+    NextTab,
+    PrevTab,
+    FocusLeft,
+    FocusRight,
+    Up,
+    Down,
+    First,
+    Last,
+    Percent,
+    HalfPageDown,
+    HalfPageUp,
+    PageDown,
+    PageUp,
+    GotoAdjacent,
+    NextMatch,
+    PrevMatch,
+    ChainNext,
+    ChainPrev,
+    NearestSink,
+    NearestSource,
+    JumpBack,
+    JumpForward,
+    Mark,
+    GotoMark,
+    GrowNodes,
+    ShrinkNodes,
+    GrowAdjacent,
+    ShrinkAdjacent,
+    FlipLayout,
+    ResetLayout,
+    Maximize,
+    ToggleAdjacent,
+    ToggleAttrs,
+    CycleAttrs,
+    ToggleLabels,
+    ScrollLeft,
+    ScrollRight,
+    ToggleElide,
+    ToggleMinimap,
+    ShowLegend,
+    ShowMessages,
+    YankId,
+    YankAttrs,
+    YankDot,
+    GotoCluster,
+    OpenCluster,
+    Complete,
+    Execute,
+    ApplySearch,
+    DeleteChar,
+    CursorLeft,
+    CursorRight,
+    Fold,
+    Unfold,
+    OpenSubgraph,
+    FilterSubgraphs,
+    SortSubgraphs,
+    ExpandAll,
+    CollapseAll,
+    ApplyFilter,
+    ClearFilter,
+    DeleteMark,
+    GotoSelectedMark,
+    GotoSelectedTab,
+    RederiveTab,
+    CloseBranch,
+    SelectLeft,
+    SelectRight,
+    SketchDeeper,
+    SketchShallower,
+    GotoSketched,
+}
+
+/// Names and descriptions of actions, in the order shown in help.
+const ACTIONS: &[(Action, &str, &str)] = &[
+    (Action::Quit, "quit", "quit dot-viewer"),
+    (Action::Cancel, "cancel", "go back to Normal mode, cancelling the running job"),
+    (Action::SearchFuzzy, "search-fuzzy", "go to fuzzy search mode"),
+    (Action::SearchRegex, "search-regex", "go to regex search mode"),
+    (Action::CommandMode, "command-mode", "go to command mode"),
+    (Action::CloseTab, "close-tab", "close the current tab (view)"),
+    (Action::ReopenTab, "reopen-tab", "reopen the latest closed tab (view)"),
+    (Action::ShowTabs, "show-tabs", "show the tree of tabs (views) derived from each other"),
+    (Action::SplitScreen, "split-screen", "show another tab (view) beside, or restore the screen"),
+    (Action::SwitchPane, "switch-pane", "switch to the other pane of the split screen"),
+    (Action::SyncSelection, "sync-selection", "select the same node in both panes, or stop it"),
+    (Action::NextTab, "next-tab", "move to the next tab"),
+    (Action::PrevTab, "prev-tab", "move to the previous tab"),
+    (Action::FocusLeft, "focus-left", "move focus to the list on the left"),
+    (Action::FocusRight, "focus-right", "move focus to the list on the right"),
+    (Action::Up, "up", "move up [count] times in focused list"),
+    (Action::Down, "down", "move down [count] times in focused list"),
+    (Action::First, "first", "go to the first, or the [count]-th, in focused list"),
+    (Action::Last, "last", "go to the last, or the [count]-th, in focused list"),
+    (Action::Percent, "percent", "go to [count] percent of focused list"),
+    (Action::HalfPageDown, "half-page-down", "scroll half a page down in focused list"),
+    (Action::HalfPageUp, "half-page-up", "scroll half a page up in focused list"),
+    (Action::PageDown, "page-down", "scroll a page down in focused list"),
+    (Action::PageUp, "page-up", "scroll a page up in focused list"),
+    (Action::GotoAdjacent, "goto-adjacent", "go to the selected prev/next node"),
+    (Action::NextMatch, "next-match", "go to next match"),
+    (Action::PrevMatch, "prev-match", "go to previous match"),
+    (Action::ChainNext, "chain-next", "follow the chain of nexts to a branch, [count] times"),
+    (Action::ChainPrev, "chain-prev", "follow the chain of prevs to a merge point, [count] times"),
+    (Action::NearestSink, "nearest-sink", "go to the nearest sink"),
+    (Action::NearestSource, "nearest-source", "go to the nearest source"),
+    (Action::JumpBack, "jump-back", "go back in the jump list"),
+    (Action::JumpForward, "jump-forward", "go forward in the jump list"),
+    (Action::Mark, "mark", "mark the current node as [a-z]"),
+    (Action::GotoMark, "goto-mark", "go to the node marked as [a-z], or before the latest jump"),
+    (Action::GrowNodes, "grow-nodes", "widen the nodes list"),
+    (Action::ShrinkNodes, "shrink-nodes", "narrow the nodes list"),
+    (Action::GrowAdjacent, "grow-adjacent", "enlarge the prev/next lists against the attrs"),
+    (Action::ShrinkAdjacent, "shrink-adjacent", "shrink the prev/next lists against the attrs"),
+    (Action::FlipLayout, "flip-layout", "put the nodes list beside or above the other panes"),
+    (Action::ResetLayout, "reset-layout", "reset the layout to default"),
+    (Action::Maximize, "maximize", "maximize or restore the focused pane"),
+    (Action::ToggleAdjacent, "toggle-adjacent", "show or hide the prev/next lists"),
+    (Action::ToggleAttrs, "toggle-attrs", "show or hide the attrs panel"),
+    (
+        Action::GotoCluster,
+        "goto-cluster",
+        "select the subgraph of the current node in the subgraph popup",
+    ),
+    (Action::OpenCluster, "open-cluster", "open the subgraph of the current node as a new tab"),
+    (Action::CycleAttrs, "cycle-attrs", "show the label, all attrs or raw dot in the attrs panel"),
+    (Action::ToggleLabels, "toggle-labels", "show nodes rendered from the template, or ids"),
+    (Action::ScrollLeft, "scroll-left", "scroll the focused list to the left"),
+    (Action::ScrollRight, "scroll-right", "scroll the focused list to the right"),
+    (Action::ToggleElide, "toggle-elide", "elide long items of node lists in the middle, or not"),
+    (Action::ToggleMinimap, "toggle-minimap", "show or hide the minimap beside the nodes list"),
+    (Action::ShowLegend, "show-legend", "show the colors of nodes in dot and their counts"),
+    (Action::ShowMessages, "show-messages", "show the log of past results and errors"),
+    (Action::YankId, "yank-id", "copy the id of the selected node to the clipboard"),
+    (Action::YankAttrs, "yank-attrs", "copy the selected node in dot to the clipboard"),
+    (Action::YankDot, "yank-dot", "copy the current tab (view) in dot to the clipboard"),
+    (Action::Complete, "complete", "autocomplete input"),
+    (Action::Execute, "execute", "execute command"),
+    (Action::ApplySearch, "apply-search", "apply search"),
+    (Action::DeleteChar, "delete-char", "delete a character of input"),
+    (Action::CursorLeft, "cursor-left", "move cursor left in input"),
+    (Action::CursorRight, "cursor-right", "move cursor right in input"),
+    (Action::Fold, "fold", "fold the selected subgraph"),
+    (Action::Unfold, "unfold", "unfold the selected subgraph"),
+    (Action::OpenSubgraph, "open-subgraph", "change root to the selected subgraph"),
+    (Action::FilterSubgraphs, "filter-subgraphs", "filter subgraphs by typing a part of their ids"),
+    (Action::SortSubgraphs, "sort-subgraphs", "sort subgraphs by name or by number of nodes"),
+    (Action::ExpandAll, "expand-all", "unfold all subgraphs"),
+    (Action::CollapseAll, "collapse-all", "fold all subgraphs"),
+    (Action::ApplyFilter, "apply-filter", "stop typing the filter"),
+    (Action::ClearFilter, "clear-filter", "clear the filter"),
+    (Action::DeleteMark, "delete-mark", "delete the selected mark"),
+    (Action::GotoSelectedMark, "goto-selected-mark", "go to the selected mark"),
+    (Action::GotoSelectedTab, "goto-selected-tab", "switch to the selected tab"),
+    (Action::RederiveTab, "rederive-tab", "derive the selected tab again with different arguments"),
+    (Action::CloseBranch, "close-branch", "close the selected tab and the tabs derived from it"),
+    (Action::SelectLeft, "select-left", "select the node on the left"),
+    (Action::SelectRight, "select-right", "select the node on the right"),
+    (Action::SketchDeeper, "sketch-deeper", "increase the depth of the sketch"),
+    (Action::SketchShallower, "sketch-shallower", "decrease the depth of the sketch"),
+    (Action::GotoSketched, "goto-sketched", "go to the selected node, sketching its neighborhood"),
+];
+
+/// `Context` is a set of keybindings, active in the corresponding mode.
+/// Bindings of `Popup` are shared by all popups, below the bindings of each popup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Context {
+    Normal,
+    Search,
+    Command,
+    Popup,
+    Subgraph,
+    SubgraphFilter,
+    Marks,
+    Lineage,
+    Sketch,
+    Help,
+}
+
+/// Names and titles of contexts, in the order shown in help.
+const CONTEXTS: &[(Context, &str, &str)] = &[
+    (Context::Normal, "normal", "Normal"),
+    (Context::Search, "search", "Search"),
+    (Context::Command, "command", "Command"),
+    (Context::Popup, "popup", "Popup"),
+    (Context::Subgraph, "subgraph", "Subgraph Popup"),
+    (Context::SubgraphFilter, "subgraph-filter", "Subgraph Filter"),
+    (Context::Marks, "marks", "Marks Popup"),
+    (Context::Lineage, "tabs", "Tabs Popup"),
+    (Context::Sketch, "sketch", "Sketch Popup"),
+    (Context::Help, "help", "Help Popup"),
+];
+
+/// Default keybindings.
+const DEFAULTS: &[(Context, &str, Action)] = &[
+    (Context::Normal, "q", Action::Quit),
+    (Context::Normal, "/", Action::SearchFuzzy),
+    (Context::Normal, "r", Action::SearchRegex),
+    (Context::Normal, ":", Action::CommandMode),
+    (Context::Normal, "c", Action::CloseTab),
+    (Context::Normal, "C", Action::ReopenTab),
+    (Context::Normal, "T", Action::ShowTabs),
+    (Context::Normal, "|", Action::SplitScreen),
+    (Context::Normal, "w", Action::SwitchPane),
+    (Context::Normal, "L", Action::SyncSelection),
+    (Context::Normal, "tab", Action::NextTab),
+    (Context::Normal, "backtab", Action::PrevTab),
+    (Context::Normal, "h", Action::FocusLeft),
+    (Context::Normal, "left", Action::FocusLeft),
+    (Context::Normal, "l", Action::FocusRight),
+    (Context::Normal, "right", Action::FocusRight),
+    (Context::Normal, "k", Action::Up),
+    (Context::Normal, "up", Action::Up),
+    (Context::Normal, "j", Action::Down),
+    (Context::Normal, "down", Action::Down),
+    (Context::Normal, "gg", Action::First),
+    (Context::Normal, "G", Action::Last),
+    (Context::Normal, "%", Action::Percent),
+    (Context::Normal, "ctrl-d", Action::HalfPageDown),
+    (Context::Normal, "ctrl-u", Action::HalfPageUp),
+    (Context::Normal, "ctrl-f", Action::PageDown),
+    (Context::Normal, "ctrl-b", Action::PageUp),
+    (Context::Normal, "enter", Action::GotoAdjacent),
+    (Context::Normal, "n", Action::NextMatch),
+    (Context::Normal, "N", Action::PrevMatch),
+    (Context::Normal, "}", Action::ChainNext),
+    (Context::Normal, "{", Action::ChainPrev),
+    (Context::Normal, "]", Action::NearestSink),
+    (Context::Normal, "[", Action::NearestSource),
+    (Context::Normal, "ctrl-o", Action::JumpBack),
+    (Context::Normal, "ctrl-i", Action::JumpForward),
+    (Context::Normal, "m", Action::Mark),
+    (Context::Normal, "'", Action::GotoMark),
+    (Context::Normal, ">", Action::GrowNodes),
+    (Context::Normal, "<", Action::ShrinkNodes),
+    (Context::Normal, "+", Action::GrowAdjacent),
+    (Context::Normal, "-", Action::ShrinkAdjacent),
+    (Context::Normal, "v", Action::FlipLayout),
+    (Context::Normal, "=", Action::ResetLayout),
+    (Context::Normal, "z", Action::Maximize),
+    (Context::Normal, "E", Action::ToggleAdjacent),
+    (Context::Normal, "A", Action::ToggleAttrs),
+    (Context::Normal, "t", Action::CycleAttrs),
+    (Context::Normal, "i", Action::ToggleLabels),
+    (Context::Normal, "(", Action::ScrollLeft),
+    (Context::Normal, ")", Action::ScrollRight),
+    (Context::Normal, "e", Action::ToggleElide),
+    (Context::Normal, "M", Action::ToggleMinimap),
+    (Context::Normal, "#", Action::ShowLegend),
+    (Context::Normal, "g<", Action::ShowMessages),
+    (Context::Normal, "yy", Action::YankId),
+    (Context::Normal, "ya", Action::YankAttrs),
+    (Context::Normal, "yd", Action::YankDot),
+    (Context::Normal, "s", Action::GotoCluster),
+    (Context::Normal, "S", Action::OpenCluster),
+    (Context::Normal, "esc", Action::Cancel),
+    (Context::Search, "esc", Action::Cancel),
+    (Context::Search, "tab", Action::Complete),
+    (Context::Search, "enter", Action::ApplySearch),
+    (Context::Search, "backspace", Action::DeleteChar),
+    (Context::Search, "left", Action::CursorLeft),
+    (Context::Search, "right", Action::CursorRight),
+    (Context::Command, "esc", Action::Cancel),
+    (Context::Command, "tab", Action::Complete),
+    (Context::Command, "enter", Action::Execute),
+    (Context::Command, "backspace", Action::DeleteChar),
+    (Context::Popup, "esc", Action::Cancel),
+    (Context::Popup, "k", Action::Up),
+    (Context::Popup, "up", Action::Up),
+    (Context::Popup, "j", Action::Down),
+    (Context::Popup, "down", Action::Down),
+    (Context::Popup, "gg", Action::First),
+    (Context::Popup, "G", Action::Last),
+    (Context::Popup, "%", Action::Percent),
+    (Context::Popup, "ctrl-d", Action::HalfPageDown),
+    (Context::Popup, "ctrl-u", Action::HalfPageUp),
+    (Context::Popup, "ctrl-f", Action::PageDown),
+    (Context::Popup, "ctrl-b", Action::PageUp),
+    (Context::Subgraph, "h", Action::Fold),
+    (Context::Subgraph, "left", Action::Fold),
+    (Context::Subgraph, "l", Action::Unfold),
+    (Context::Subgraph, "right", Action::Unfold),
+    (Context::Subgraph, "enter", Action::OpenSubgraph),
+    (Context::Subgraph, "/", Action::FilterSubgraphs),
+    (Context::Subgraph, "o", Action::SortSubgraphs),
+    (Context::Subgraph, "+", Action::ExpandAll),
+    (Context::Subgraph, "-", Action::CollapseAll),
+    (Context::SubgraphFilter, "enter", Action::ApplyFilter),
+    (Context::SubgraphFilter, "esc", Action::ClearFilter),
+    (Context::SubgraphFilter, "backspace", Action::DeleteChar),
+    (Context::SubgraphFilter, "up", Action::Up),
+    (Context::SubgraphFilter, "down", Action::Down),
+    (Context::Marks, "d", Action::DeleteMark),
+    (Context::Marks, "enter", Action::GotoSelectedMark),
+    (Context::Lineage, "enter", Action::GotoSelectedTab),
+    (Context::Lineage, "r", Action::RederiveTab),
+    (Context::Lineage, "d", Action::CloseBranch),
+    (Context::Sketch, "h", Action::SelectLeft),
+    (Context::Sketch, "left", Action::SelectLeft),
+    (Context::Sketch, "l", Action::SelectRight),
+    (Context::Sketch, "right", Action::SelectRight),
+    (Context::Sketch, "+", Action::SketchDeeper),
+    (Context::Sketch, "-", Action::SketchShallower),
+    (Context::Sketch, "enter", Action::GotoSketched),
+];
+
+/// Names of special keys.
+const KEYS: &[(KeyCode, &str)] = &[
+    (KeyCode::Enter, "enter"),
+    (KeyCode::Esc, "esc"),
+    (KeyCode::Tab, "tab"),
+    (KeyCode::BackTab, "backtab"),
+    (KeyCode::Backspace, "backspace"),
+    (KeyCode::Delete, "delete"),
+    (KeyCode::Up, "up"),
+    (KeyCode::Down, "down"),
+    (KeyCode::Left, "left"),
+    (KeyCode::Right, "right"),
+    (KeyCode::Home, "home"),
+    (KeyCode::End, "end"),
+    (KeyCode::PageUp, "pageup"),
+    (KeyCode::PageDown, "pagedown"),
+    (KeyCode::Char(' '), "space"),
+];
+
+/// `Chord` is a key pressed with modifiers, normalized such that
+/// only `ctrl` and `alt` are kept, as `shift` is already reflected in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Chord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+/// Result of looking up typed keys in a keymap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Lookup {
+    /// Keys are bound to the action
+    Action(Action),
+    /// Keys are a prefix of some bindings, e.g., `g` of `gg`
+    Prefix,
+    /// Keys are not bound
+    None,
+}
+
+/// `Keymap` maps sequences of key chords to actions in each context.
+///
+/// Default bindings are overridden by user bindings in `[keys.<context>]` tables of the config,
+/// where binding a key to `"none"` unbinds it.
+pub(crate) struct Keymap {
+    bindings: HashMap<Context, Vec<(Vec<Chord>, Action)>>,
+}
+
+impl Keymap {
+    pub fn new(keys: &HashMap<String, HashMap<String, String>>) -> DotViewerResult<Self> {
+        let mut keymap = Self { bindings: HashMap::new() };
+
+        for &(context, keys, action) in DEFAULTS {
+            let keys = parse_keys(keys).expect("default keys should be valid");
+            keymap.bind(context, keys, Some(action));
+        }
+
+        for (context, bindings) in keys {
+            let context: Context = context.parse()?;
+
+            for (keys, action) in bindings {
+                let keys = parse_keys(keys)?;
+                let action = match action.as_str() {
+                    "none" => None,
+                    action => Some(action.parse()?),
+                };
+
+                keymap.bind(context, keys, action);
+            }
+        }
+
+        Ok(keymap)
+    }
+
+    /// Look up the typed `keys` in `contexts`, in order of precedence.
+    pub fn lookup(&self, contexts: &[Context], keys: &[Chord]) -> Lookup {
+        let bindings = || contexts.iter().flat_map(|context| self.bindings(*context));
+
+        if let Some((_, action)) = bindings().find(|(bound, _)| bound == keys) {
+            return Lookup::Action(*action);
+        }
+
+        if bindings().any(|(bound, _)| bound.starts_with(keys)) {
+            return Lookup::Prefix;
+        }
+
+        Lookup::None
+    }
+
+    /// Bindings in `context`, in order of definition.
+    pub fn bindings(&self, context: Context) -> &[(Vec<Chord>, Action)] {
+        self.bindings.get(&context).map_or(&[], Vec::as_slice)
+    }
+
+    fn bind(&mut self, context: Context, keys: Vec<Chord>, action: Option<Action>) {
+        let bindings = self.bindings.entry(context).or_default();
+
+        match (bindings.iter().position(|(bound, _)| *bound == keys), action) {
+            (Some(idx), Some(action)) => bindings[idx].1 = action,
+            (Some(idx), None) => {
+                bindings.remove(idx);
+            }
+            (None, Some(action)) => bindings.push((keys, action)),
+            (None, None) => {}
+        }
+    }
+}
+
+impl Action {
+    /// Whether the action takes the next typed character as its argument, e.g., `ma`.
+    pub fn takes_char(&self) -> bool {
+        matches!(self, Self::Mark | Self::GotoMark)
+    }
+
+    pub fn description(&self) -> &'static str {
+        let (_, _, description) = ACTIONS.iter().find(|(action, _, _)| action == self).unwrap();
+        description
+    }
+
+    /// All actions, in the order shown in help.
+    pub fn all() -> impl Iterator<Item = Action> {
+        ACTIONS.iter().map(|(action, _, _)| *action)
+    }
+}
+
+impl FromStr for Action {
+    type Err = DotViewerError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        (ACTIONS.iter())
+            .find(|(_, n, _)| *n == name)
+            .map(|(action, _, _)| *action)
+            .ok_or(DotViewerError::ConfigError(format!("no such action {name}")))
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (_, name, _) = ACTIONS.iter().find(|(action, _, _)| action == self).unwrap();
+        write!(f, "{name}")
+    }
+}
+
+impl Context {
+    /// Contexts active in `mode`, in order of precedence.
+    pub fn of(mode: &Mode) -> &'static [Context] {
+        match mode {
+            Mode::Normal => &[Context::Normal],
+            Mode::Search(_) => &[Context::Search],
+            Mode::Command => &[Context::Command],
+            Mode::Popup(pmode) => match pmode {
+                PopupMode::Tree => &[Context::Subgraph, Context::Popup],
+                PopupMode::TreeFilter => &[Context::SubgraphFilter],
+                PopupMode::Marks => &[Context::Marks, Context::Popup],
+                PopupMode::Lineage => &[Context::Lineage, Context::Popup],
+                PopupMode::Legend | PopupMode::Messages => &[Context::Popup],
+                PopupMode::Sketch => &[Context::Sketch, Context::Popup],
+                PopupMode::Help => &[Context::Help, Context::Popup],
+            },
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        let (_, _, title) = CONTEXTS.iter().find(|(context, _, _)| context == self).unwrap();
+        title
+    }
+
+    /// All contexts, in the order shown in help.
+    pub fn all() -> impl Iterator<Item = Context> {
+        CONTEXTS.iter().map(|(context, _, _)| *context)
+    }
+}
+
+impl FromStr for Context {
+    type Err = DotViewerError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        (CONTEXTS.iter())
+            .find(|(_, n, _)| *n == name)
+            .map(|(context, _, _)| *context)
+            .ok_or(DotViewerError::ConfigError(format!("no such keybinding context {name}")))
+    }
+}
+
+impl From<KeyEvent> for Chord {
+    fn from(key: KeyEvent) -> Self {
+        let modifiers = key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+
+        Self { code: key.code, modifiers }
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt-")?;
+        }
+
+        match KEYS.iter().find(|(code, _)| *code == self.code) {
+            Some((_, name)) => write!(f, "{name}"),
+            None => match self.code {
+                KeyCode::Char(c) => write!(f, "{c}"),
+                KeyCode::F(n) => write!(f, "f{n}"),
+                code => write!(f, "{code:?}"),
+            },
+        }
+    }
+}
+
+/// Parse keys, which are either a chord like `j`, `ctrl-d` and `enter`,
+/// or a sequence of characters like `gg`.
+pub(crate) fn parse_keys(keys: &str) -> DotViewerResult<Vec<Chord>> {
+    if let Some(chord) = parse_chord(keys) {
+        return Ok(vec![chord]);
+    }
+
+    let chords: Vec<Chord> = (keys.chars())
+        .map(|c| Chord { code: KeyCode::Char(c), modifiers: KeyModifiers::NONE })
+        .collect();
+
+    if chords.len() < 2 || keys.chars().any(char::is_whitespace) {
+        return Err(DotViewerError::ConfigError(format!("invalid keys {keys:?}")));
+    }
+
+    Ok(chords)
+}
+
+fn parse_chord(chord: &str) -> Option<Chord> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut key = chord;
+
+    loop {
+        if let Some(rest) = key.strip_prefix("ctrl-").filter(|rest| !rest.is_empty()) {
+            modifiers |= KeyModifiers::CONTROL;
+            key = rest;
+        } else if let Some(rest) = key.strip_prefix("alt-").filter(|rest| !rest.is_empty()) {
+            modifiers |= KeyModifiers::ALT;
+            key = rest;
+        } else {
+            break;
+        }
+    }
+
+    let code = match KEYS.iter().find(|(_, name)| *name == key) {
+        Some((code, _)) => *code,
+        None => {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                (Some('f'), Some(_)) => KeyCode::F(key[1..].parse().ok()?),
+                _ => return None,
+            }
+        }
+    };
+
+    Some(Chord { code, modifiers })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(code: KeyCode, modifiers: KeyModifiers) -> Chord {
+        Chord { code, modifiers }
+    }
+
+    fn keymap(context: &str, bindings: &[(&str, &str)]) -> Keymap {
+        let bindings = bindings.iter().map(|(k, a)| (k.to_string(), a.to_string())).collect();
+        Keymap::new(&HashMap::from([(context.to_string(), bindings)])).unwrap()
+    }
+
+    #[test]
+    fn parse_chords() {
+        let none = KeyModifiers::NONE;
+
+        assert_eq!(parse_keys("j").unwrap(), [chord(KeyCode::Char('j'), none)]);
+        assert_eq!(parse_keys("enter").unwrap(), [chord(KeyCode::Enter, none)]);
+        assert_eq!(parse_keys("space").unwrap(), [chord(KeyCode::Char(' '), none)]);
+        assert_eq!(parse_keys("f5").unwrap(), [chord(KeyCode::F(5), none)]);
+        assert_eq!(
+            parse_keys("ctrl-d").unwrap(),
+            [chord(KeyCode::Char('d'), KeyModifiers::CONTROL)]
+        );
+        assert_eq!(
+            parse_keys("ctrl-alt-left").unwrap(),
+            [chord(KeyCode::Left, KeyModifiers::CONTROL | KeyModifiers::ALT)]
+        );
+        assert_eq!(parse_keys("-").unwrap(), [chord(KeyCode::Char('-'), none)]);
+    }
+
+    #[test]
+    fn parse_sequences() {
+        let g = chord(KeyCode::Char('g'), KeyModifiers::NONE);
+
+        assert_eq!(parse_keys("gg").unwrap(), [g, g]);
+        assert!(parse_keys("").is_err());
+        assert!(parse_keys("g g").is_err());
+        assert!(parse_keys("fx").unwrap().iter().all(|c| matches!(c.code, KeyCode::Char(_))));
+    }
+
+    #[test]
+    fn display_default_keys() {
+        for (_, keys, _) in DEFAULTS {
+            let chords = parse_keys(keys).unwrap();
+            let shown: String = chords.iter().map(|chord| chord.to_string()).collect();
+            assert_eq!(shown, *keys);
+        }
+    }
+
+    #[test]
+    fn lookup_bindings() {
+        let keymap = Keymap::new(&HashMap::new()).unwrap();
+        let lookup = |keys: &str| keymap.lookup(&[Context::Normal], &parse_keys(keys).unwrap());
+
+        assert_eq!(lookup("j"), Lookup::Action(Action::Down));
+        assert_eq!(lookup("gg"), Lookup::Action(Action::First));
+        assert_eq!(lookup("g"), Lookup::Prefix);
+        assert_eq!(lookup("ctrl-f5"), Lookup::None);
+    }
+
+    #[test]
+    fn override_bindings() {
+        let keymap = keymap("normal", &[("j", "none"), ("ctrl-n", "down"), ("k", "down")]);
+        let lookup = |keys: &str| keymap.lookup(&[Context::Normal], &parse_keys(keys).unwrap());
+
+        assert_eq!(lookup("j"), Lookup::None);
+        assert_eq!(lookup("down"), Lookup::Action(Action::Down));
+        assert_eq!(lookup("ctrl-n"), Lookup::Action(Action::Down));
+        assert_eq!(lookup("k"), Lookup::Action(Action::Down));
+    }
+
+    #[test]
+    fn lookup_by_precedence() {
+        let keymap = keymap("marks", &[("j", "delete-mark")]);
+        let lookup = |contexts: &[Context]| keymap.lookup(contexts, &parse_keys("j").unwrap());
+
+        assert_eq!(lookup(&[Context::Marks, Context::Popup]), Lookup::Action(Action::DeleteMark));
+        assert_eq!(lookup(&[Context::Lineage, Context::Popup]), Lookup::Action(Action::Down));
+    }
+
+    #[test]
+    fn reject_invalid_config() {
+        let keys = |context: &str, keys: &str, action: &str| {
+            let bindings = HashMap::from([(keys.to_string(), action.to_string())]);
+            Keymap::new(&HashMap::from([(context.to_string(), bindings)]))
+        };
+
+        assert!(keys("normal", "j", "down").is_ok());
+        assert!(keys("nowhere", "j", "down").is_err());
+        assert!(keys("normal", "j", "fly").is_err());
+        assert!(keys("normal", "a b", "down").is_err());
+    }
+}
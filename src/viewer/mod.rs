@@ -1,15 +1,25 @@
 mod app;
 mod command;
+mod config;
 mod error;
 mod help;
 mod keybindings;
+mod keymap;
 mod modes;
+mod mouse;
+mod panes;
 mod success;
+mod theme;
 mod utils;
 mod view;
 
 pub(crate) use crate::viewer::{
     app::App,
+    config::Config,
+    error::DotViewerError,
     modes::{Mode, PopupMode, SearchMode},
+    panes::{AttrsMode, Orientation, Panes},
+    theme::Theme,
+    utils::{htmlparser, label_lines, Job},
     view::{Focus, View},
 };
@@ -9,10 +9,21 @@ pub(crate) enum Mode {
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// In `PopupMode`, users can
-/// - navigate the subgraphs, or
+/// - navigate the subgraphs, filtering them by typing in `TreeFilter`,
+/// - navigate the marks,
+/// - navigate the derivation tree of tabs,
+/// - navigate the legend of node colors,
+/// - navigate the log of past messages,
+/// - navigate the sketch of the neighborhood, or
 /// - see help message.
 pub(crate) enum PopupMode {
     Tree,
+    TreeFilter,
+    Marks,
+    Lineage,
+    Legend,
+    Messages,
+    Sketch,
     Help,
 }
 
@@ -0,0 +1,216 @@
+use crate::viewer::{
+    app::App,
+    error::DotViewerResult,
+    modes::{Mode, PopupMode},
+    success::Success,
+    view::Focus,
+};
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+/// Maximum interval between two clicks at the same position to be a double click
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+/// Number of items scrolled by a wheel notch
+const WHEEL: isize = 3;
+
+impl App {
+    pub fn mouse(&mut self, event: MouseEvent) {
+        let (x, y) = (event.column, event.row);
+
+        let res = match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let double = self.double_click(x, y);
+                self.click(x, y, double)
+            }
+            MouseEventKind::ScrollDown => self.wheel(x, y, WHEEL),
+            MouseEventKind::ScrollUp => self.wheel(x, y, -WHEEL),
+            _ => return,
+        };
+
+        self.result = res.map(|_| Success::default());
+        self.sync_selection();
+        self.log_result();
+
+        self.count = None;
+    }
+
+    /// Record a click at column `x` and row `y`,
+    /// returning whether it completes a double click.
+    fn double_click(&mut self, x: u16, y: u16) -> bool {
+        let now = Instant::now();
+
+        let double = matches!(
+            self.click,
+            Some((instant, cx, cy)) if (cx, cy) == (x, y) && now - instant <= DOUBLE_CLICK
+        );
+
+        // a third click starts over instead of being another double click
+        self.click = if double { None } else { Some((now, x, y)) };
+
+        double
+    }
+
+    fn click(&mut self, x: u16, y: u16, double: bool) -> DotViewerResult<()> {
+        match &self.mode {
+            Mode::Normal => self.click_normal(x, y, double),
+            Mode::Popup(pmode) => match pmode {
+                PopupMode::Tree | PopupMode::TreeFilter => self.click_tree(x, y, double),
+                PopupMode::Marks => self.click_marks(x, y, double),
+                PopupMode::Lineage => self.click_lineage(x, y, double),
+                PopupMode::Legend => self.click_legend(x, y),
+                PopupMode::Messages => {
+                    if let Some(idx) = self.messages.list.hit(x, y) {
+                        self.messages.list.select(idx);
+                    }
+                    Ok(())
+                }
+                PopupMode::Sketch => self.click_sketch(x, y, double),
+                PopupMode::Help => {
+                    if let Some(idx) = self.help.hit(x, y) {
+                        self.help.nth(idx);
+                    }
+                    Ok(())
+                }
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn click_normal(&mut self, x: u16, y: u16, double: bool) -> DotViewerResult<()> {
+        let widths = self.tabs.tabs.iter().map(|view| view.short_title().chars().count());
+        if let Some(idx) = self.tabs.hit(widths, x, y) {
+            self.tabs.select(idx);
+            return Ok(());
+        }
+
+        if self.tabs.hit_other(x, y) {
+            self.tabs.switch()?;
+        }
+
+        let view = self.tabs.selected();
+
+        if let Some(idx) = view.current.hit(x, y) {
+            view.focus = Focus::Current;
+            let id = view.current.items[idx].clone();
+            return view.goto(&id);
+        }
+
+        if let Some(idx) = view.minimap_hit(x, y) {
+            view.focus = Focus::Current;
+            let id = view.current.items[idx].clone();
+            return view.goto(&id);
+        }
+
+        if let Some(idx) = view.prevs.hit(x, y) {
+            view.focus = Focus::Prev;
+            view.prevs.select(idx);
+        } else if let Some(idx) = view.nexts.hit(x, y) {
+            view.focus = Focus::Next;
+            view.nexts.select(idx);
+        } else if view.attrs.contains(x, y) {
+            view.focus = Focus::Attrs;
+            return Ok(());
+        } else {
+            return Ok(());
+        }
+
+        if double {
+            view.goto_adjacent()?;
+        }
+
+        Ok(())
+    }
+
+    fn click_tree(&mut self, x: u16, y: u16, double: bool) -> DotViewerResult<()> {
+        let subtree = &mut self.tabs.selected().subtree;
+
+        if let Some(idx) = subtree.hit(x, y) {
+            subtree.nth(idx);
+            if double {
+                subtree.toggle();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn click_marks(&mut self, x: u16, y: u16, double: bool) -> DotViewerResult<()> {
+        match self.marks.list.hit(x, y) {
+            Some(idx) => {
+                self.marks.list.select(idx);
+                if double {
+                    self.goto_selected_mark()?;
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn click_lineage(&mut self, x: u16, y: u16, double: bool) -> DotViewerResult<()> {
+        match self.lineage.list.hit(x, y) {
+            Some(idx) => {
+                self.lineage.list.select(idx);
+                if double {
+                    self.goto_selected_tab()?;
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn click_legend(&mut self, x: u16, y: u16) -> DotViewerResult<()> {
+        if let Some(idx) = self.legend.hit(x, y) {
+            self.legend.select(idx);
+        }
+        Ok(())
+    }
+
+    fn click_sketch(&mut self, x: u16, y: u16, double: bool) -> DotViewerResult<()> {
+        let sketch = self.sketch_mut()?;
+
+        match sketch.hit(x, y) {
+            Some(selected) => {
+                sketch.selected = selected;
+                if double {
+                    self.goto_sketched()?;
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Scroll the pane under column `x` and row `y` by `offset`, focusing it in the main screen.
+    fn wheel(&mut self, x: u16, y: u16, offset: isize) -> DotViewerResult<()> {
+        match &self.mode {
+            Mode::Normal => {
+                if self.tabs.hit_other(x, y) {
+                    self.tabs.switch()?;
+                }
+
+                let view = self.tabs.selected();
+
+                view.focus = if view.current.contains(x, y) || view.minimap_hit(x, y).is_some() {
+                    Focus::Current
+                } else if view.prevs.contains(x, y) {
+                    Focus::Prev
+                } else if view.nexts.contains(x, y) {
+                    Focus::Next
+                } else if view.attrs.contains(x, y) {
+                    Focus::Attrs
+                } else {
+                    return Ok(());
+                };
+
+                view.scroll(offset)
+            }
+            Mode::Popup(_) => self.scroll_by(offset),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::viewer::{
+    config::config_dir,
+    error::{DotViewerError, DotViewerResult},
+    view::Focus,
+};
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Step of resizing panes, in percentage
+const STEP: u16 = 5;
+/// Minimum size of a pane, in percentage
+const MIN: u16 = 10;
+/// Maximum size of a pane, in percentage
+const MAX: u16 = 90;
+
+/// Whether the nodes list is beside, or above the other panes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+/// What the attrs panel shows for the current node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AttrsMode {
+    /// Decoded label
+    Label,
+    /// All attributes, and those of the enclosing graph and subgraphs
+    Table,
+    /// Raw dot source
+    Raw,
+}
+
+impl AttrsMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Label => "label",
+            Self::Table => "table",
+            Self::Raw => "raw",
+        }
+    }
+}
+
+/// `Panes` holds the layout of panes in the main screen.
+///
+/// The layout is remembered in `$XDG_CONFIG_HOME/dot-viewer/layout.toml`
+/// (or `~/.config/dot-viewer/layout.toml`) whenever it is changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Panes {
+    pub orientation: Orientation,
+    /// Size of the nodes list against the other panes, in percentage
+    pub nodes: u16,
+    /// Size of the prev and next lists against the attrs panel, in percentage
+    pub adjacent: u16,
+    /// Whether to show the prev and next lists
+    pub show_adjacent: bool,
+    /// Whether to show the attrs panel
+    pub show_attrs: bool,
+    /// What the attrs panel shows
+    pub attrs: AttrsMode,
+    /// Whether node lists show items rendered from the template instead of ids
+    pub labeled: bool,
+    /// Whether long items of node lists are elided in the middle, instead of scrolled horizontally
+    pub elided: bool,
+    /// Whether to show the minimap beside the nodes list
+    pub minimap: bool,
+    /// Whether to show only the focused pane, which is not remembered
+    #[serde(skip)]
+    pub maximized: bool,
+}
+
+impl Default for Panes {
+    fn default() -> Self {
+        Self {
+            orientation: Orientation::Horizontal,
+            nodes: 35,
+            adjacent: 50,
+            show_adjacent: true,
+            show_attrs: true,
+            attrs: AttrsMode::Label,
+            labeled: false,
+            elided: false,
+            minimap: true,
+            maximized: false,
+        }
+    }
+}
+
+impl Panes {
+    /// Load the remembered layout, or the default layout if there is none.
+    /// Sizes of panes are clamped, as the file may have been edited by hand.
+    pub fn load() -> DotViewerResult<Self> {
+        match path() {
+            Some(path) if path.exists() => {
+                let contents = fs::read_to_string(&path)?;
+                let panes: Self = toml::from_str(&contents)
+                    .map_err(|e| DotViewerError::ConfigError(format!("{}: {e}", path.display())))?;
+
+                Ok(Self {
+                    nodes: panes.nodes.clamp(MIN, MAX),
+                    adjacent: panes.adjacent.clamp(MIN, MAX),
+                    ..panes
+                })
+            }
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Remember the layout.
+    pub fn save(&self) -> DotViewerResult<()> {
+        let path =
+            path().ok_or(DotViewerError::ViewerError("layout is not remembered".to_string()))?;
+
+        let contents =
+            toml::to_string(self).map_err(|e| DotViewerError::ViewerError(e.to_string()))?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    /// Grow (or shrink if `grow` is false) the nodes list by a step.
+    pub fn resize_nodes(&mut self, grow: bool) {
+        self.nodes = resize(self.nodes, grow);
+    }
+
+    /// Grow (or shrink if `grow` is false) the prev and next lists by a step.
+    pub fn resize_adjacent(&mut self, grow: bool) {
+        self.adjacent = resize(self.adjacent, grow);
+    }
+
+    /// Whether the pane of `focus` is shown.
+    pub fn shows(&self, focus: &Focus) -> bool {
+        match focus {
+            Focus::Current => true,
+            Focus::Prev | Focus::Next => self.show_adjacent,
+            Focus::Attrs => self.show_attrs,
+        }
+    }
+
+    pub fn cycle_attrs(&mut self) {
+        self.attrs = match self.attrs {
+            AttrsMode::Label => AttrsMode::Table,
+            AttrsMode::Table => AttrsMode::Raw,
+            AttrsMode::Raw => AttrsMode::Label,
+        };
+    }
+
+    pub fn flip(&mut self) {
+        self.orientation = match self.orientation {
+            Orientation::Horizontal => Orientation::Vertical,
+            Orientation::Vertical => Orientation::Horizontal,
+        };
+    }
+}
+
+fn resize(size: u16, grow: bool) -> u16 {
+    let size = if grow { size + STEP } else { size.saturating_sub(STEP) };
+    size.clamp(MIN, MAX)
+}
+
+fn path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("layout.toml"))
+}
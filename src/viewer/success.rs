@@ -5,6 +5,8 @@ use std::fmt;
 pub(crate) enum Success {
     ExportSuccess(String),
     XdotSuccess,
+    YankSuccess(String),
+    CancelSuccess(String),
     Silent,
 }
 
@@ -19,6 +21,8 @@ impl fmt::Display for Success {
         match &self {
             Self::ExportSuccess(filename) => write!(f, "successfully exported to {filename}"),
             Self::XdotSuccess => write!(f, "launched xdot"),
+            Self::YankSuccess(msg) => write!(f, "yanked {msg}"),
+            Self::CancelSuccess(name) => write!(f, "cancelled {name}"),
             Self::Silent => Ok(()),
         }
     }
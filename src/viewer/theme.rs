@@ -0,0 +1,314 @@
+use crate::viewer::{
+    config::ThemeConfig,
+    error::{DotViewerError, DotViewerResult},
+};
+
+use std::env;
+
+use tui::style::{Color, Modifier, Style};
+
+/// `Theme` holds named styles of `dot-viewer`.
+///
+/// A theme is one of the built-in themes, `dark`, `light`, `high-contrast` and `mono`,
+/// with styles overridden by users.
+/// If `NO_COLOR` is set, colors are dropped and styles fall back to modifiers only.
+#[derive(Debug, Clone)]
+pub(crate) struct Theme {
+    /// Borders of blocks
+    pub border: Style,
+    /// Borders of the focused block
+    pub border_focused: Style,
+    /// Titles of tabs
+    pub tab: Style,
+    /// Title of the selected tab
+    pub tab_selected: Style,
+    /// Selected item in the nodes list and the marks popup
+    pub selected: Style,
+    /// Prev nodes of the selected node, in the nodes list
+    pub prev: Style,
+    /// Next nodes of the selected node, in the nodes list
+    pub next: Style,
+    /// Selected item in the prev nodes list
+    pub prev_selected: Style,
+    /// Selected item in the next nodes list
+    pub next_selected: Style,
+    /// Characters matching the search keyword
+    pub matched: Style,
+    /// Result of a successful command
+    pub success: Style,
+    /// Result of a failed command
+    pub error: Style,
+    /// Input form in command and search mode
+    pub input: Style,
+    /// Headers of tables
+    pub header: Style,
+    /// Selected item in the subgraph tree and the sketch
+    pub popup_selected: Style,
+    /// Selected row in tables
+    pub row_selected: Style,
+    /// Accented texts, like mark names and the center of the sketch
+    pub accent: Style,
+    /// Annotations, like label summaries and degrees of prev and next nodes
+    pub hint: Style,
+    /// Whether items of node lists are styled by the colors of nodes in dot
+    pub colored: bool,
+}
+
+/// Names of built-in themes.
+const THEMES: &[&str] = &["dark", "light", "high-contrast", "mono"];
+
+const COLORS: &[(&str, Color)] = &[
+    ("reset", Color::Reset),
+    ("black", Color::Black),
+    ("red", Color::Red),
+    ("green", Color::Green),
+    ("yellow", Color::Yellow),
+    ("blue", Color::Blue),
+    ("magenta", Color::Magenta),
+    ("cyan", Color::Cyan),
+    ("gray", Color::Gray),
+    ("darkgray", Color::DarkGray),
+    ("lightred", Color::LightRed),
+    ("lightgreen", Color::LightGreen),
+    ("lightyellow", Color::LightYellow),
+    ("lightblue", Color::LightBlue),
+    ("lightmagenta", Color::LightMagenta),
+    ("lightcyan", Color::LightCyan),
+    ("white", Color::White),
+];
+
+const MODIFIERS: &[(&str, Modifier)] = &[
+    ("bold", Modifier::BOLD),
+    ("dim", Modifier::DIM),
+    ("italic", Modifier::ITALIC),
+    ("underlined", Modifier::UNDERLINED),
+    ("slow-blink", Modifier::SLOW_BLINK),
+    ("rapid-blink", Modifier::RAPID_BLINK),
+    ("reversed", Modifier::REVERSED),
+    ("hidden", Modifier::HIDDEN),
+    ("crossed-out", Modifier::CROSSED_OUT),
+];
+
+impl Theme {
+    pub fn new(config: &ThemeConfig) -> DotViewerResult<Self> {
+        let no_color = env::var_os("NO_COLOR").filter(|value| !value.is_empty()).is_some();
+
+        let name = match &config.name {
+            _ if no_color => "mono",
+            Some(name) => name.as_str(),
+            None => "dark",
+        };
+
+        let mut theme = match name {
+            "dark" => Self::dark(),
+            "light" => Self::light(),
+            "high-contrast" => Self::high_contrast(),
+            "mono" => Self::mono(),
+            _ => {
+                let msg = format!("no such theme {name}, expected one of {}", THEMES.join(", "));
+                return Err(DotViewerError::ConfigError(msg));
+            }
+        };
+
+        for (name, spec) in &config.styles {
+            let mut style = parse_style(spec)?;
+            if no_color {
+                style = Style::default().add_modifier(style.add_modifier);
+            }
+
+            let slot = (theme.style_mut(name))
+                .ok_or(DotViewerError::ConfigError(format!("no such style {name}")))?;
+            *slot = style;
+        }
+
+        Ok(theme)
+    }
+
+    fn dark() -> Self {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+
+        Self {
+            border: Style::default().fg(Color::White),
+            border_focused: Style::default().fg(Color::Yellow),
+            tab: Style::default().fg(Color::Yellow),
+            tab_selected: bold.bg(Color::Black),
+            selected: bold.fg(Color::Green),
+            prev: Style::default().fg(Color::Rgb(255, 150, 150)),
+            next: Style::default().fg(Color::Rgb(150, 150, 255)),
+            prev_selected: bold.fg(Color::Red),
+            next_selected: bold.fg(Color::Blue),
+            matched: bold.bg(Color::Rgb(120, 120, 120)),
+            success: bold.fg(Color::Green),
+            error: bold.fg(Color::Red),
+            input: Style::default().fg(Color::Yellow),
+            header: bold.fg(Color::Green),
+            popup_selected: bold.fg(Color::Black).bg(Color::LightGreen),
+            row_selected: Style::default().add_modifier(Modifier::REVERSED),
+            accent: bold.fg(Color::Yellow),
+            hint: Style::default().fg(Color::DarkGray),
+            colored: true,
+        }
+    }
+
+    fn light() -> Self {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+
+        Self {
+            border: Style::default().fg(Color::Black),
+            border_focused: bold.fg(Color::Rgb(180, 100, 0)),
+            tab: Style::default().fg(Color::Rgb(140, 80, 0)),
+            tab_selected: bold.fg(Color::Black).bg(Color::Rgb(220, 220, 220)),
+            selected: bold.fg(Color::Rgb(0, 120, 0)),
+            prev: Style::default().fg(Color::Rgb(170, 30, 30)),
+            next: Style::default().fg(Color::Rgb(30, 30, 170)),
+            prev_selected: bold.fg(Color::Rgb(200, 0, 0)),
+            next_selected: bold.fg(Color::Rgb(0, 0, 200)),
+            matched: bold.bg(Color::Rgb(200, 200, 200)),
+            success: bold.fg(Color::Rgb(0, 120, 0)),
+            error: bold.fg(Color::Rgb(200, 0, 0)),
+            input: Style::default().fg(Color::Rgb(140, 80, 0)),
+            header: bold.fg(Color::Rgb(0, 120, 0)),
+            popup_selected: bold.fg(Color::White).bg(Color::Rgb(0, 120, 0)),
+            row_selected: Style::default().add_modifier(Modifier::REVERSED),
+            accent: bold.fg(Color::Rgb(180, 100, 0)),
+            hint: Style::default().fg(Color::Rgb(120, 120, 120)),
+            colored: true,
+        }
+    }
+
+    fn high_contrast() -> Self {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+
+        Self {
+            border: Style::default().fg(Color::White),
+            border_focused: bold.fg(Color::LightYellow),
+            tab: bold.fg(Color::White),
+            tab_selected: bold.fg(Color::Black).bg(Color::LightYellow),
+            selected: bold.fg(Color::Black).bg(Color::LightGreen),
+            prev: bold.fg(Color::LightRed),
+            next: bold.fg(Color::LightCyan),
+            prev_selected: bold.fg(Color::Black).bg(Color::LightRed),
+            next_selected: bold.fg(Color::Black).bg(Color::LightCyan),
+            matched: bold.add_modifier(Modifier::UNDERLINED).fg(Color::LightYellow),
+            success: bold.fg(Color::LightGreen),
+            error: bold.fg(Color::White).bg(Color::Red),
+            input: bold.fg(Color::LightYellow),
+            header: bold.add_modifier(Modifier::UNDERLINED).fg(Color::White),
+            popup_selected: bold.fg(Color::Black).bg(Color::LightGreen),
+            row_selected: bold.add_modifier(Modifier::REVERSED),
+            accent: bold.fg(Color::LightYellow),
+            hint: Style::default().fg(Color::Gray),
+            colored: true,
+        }
+    }
+
+    /// Styles in modifiers only, for monochrome terminals and `NO_COLOR`.
+    fn mono() -> Self {
+        let plain = Style::default();
+        let bold = plain.add_modifier(Modifier::BOLD);
+        let reversed = plain.add_modifier(Modifier::REVERSED);
+
+        Self {
+            border: plain,
+            border_focused: bold,
+            tab: plain,
+            tab_selected: reversed,
+            selected: reversed,
+            prev: plain.add_modifier(Modifier::ITALIC),
+            next: plain.add_modifier(Modifier::UNDERLINED),
+            prev_selected: reversed.add_modifier(Modifier::ITALIC),
+            next_selected: reversed.add_modifier(Modifier::UNDERLINED),
+            matched: bold.add_modifier(Modifier::UNDERLINED),
+            success: bold,
+            error: bold.add_modifier(Modifier::REVERSED),
+            input: bold,
+            header: bold.add_modifier(Modifier::UNDERLINED),
+            popup_selected: reversed,
+            row_selected: reversed,
+            accent: bold,
+            hint: plain.add_modifier(Modifier::DIM),
+            colored: false,
+        }
+    }
+
+    fn style_mut(&mut self, name: &str) -> Option<&mut Style> {
+        let style = match name {
+            "border" => &mut self.border,
+            "border-focused" => &mut self.border_focused,
+            "tab" => &mut self.tab,
+            "tab-selected" => &mut self.tab_selected,
+            "selected" => &mut self.selected,
+            "prev" => &mut self.prev,
+            "next" => &mut self.next,
+            "prev-selected" => &mut self.prev_selected,
+            "next-selected" => &mut self.next_selected,
+            "matched" => &mut self.matched,
+            "success" => &mut self.success,
+            "error" => &mut self.error,
+            "input" => &mut self.input,
+            "header" => &mut self.header,
+            "popup-selected" => &mut self.popup_selected,
+            "row-selected" => &mut self.row_selected,
+            "accent" => &mut self.accent,
+            "hint" => &mut self.hint,
+            _ => return None,
+        };
+
+        Some(style)
+    }
+}
+
+/// Parse a style, which is a foreground color, `on` a background color, and modifiers,
+/// e.g., `black on lightgreen bold`, `#ff9696` or `italic`.
+pub(crate) fn parse_style(spec: &str) -> DotViewerResult<Style> {
+    let mut style = Style::default();
+    let mut tokens = spec.split_whitespace();
+
+    while let Some(token) = tokens.next() {
+        if token == "on" {
+            let color = tokens.next().ok_or(DotViewerError::ConfigError(format!(
+                "style {spec:?}: expected a background color after on"
+            )))?;
+            style = style.bg(parse_color(spec, color)?);
+        } else if let Some((_, modifier)) = MODIFIERS.iter().find(|(name, _)| *name == token) {
+            style = style.add_modifier(*modifier);
+        } else {
+            style = style.fg(parse_color(spec, token)?);
+        }
+    }
+
+    Ok(style)
+}
+
+/// Parse a color, which is a name, a `#rrggbb` hex code, or an index of 256 colors.
+fn parse_color(spec: &str, color: &str) -> DotViewerResult<Color> {
+    if let Some((_, color)) = COLORS.iter().find(|(name, _)| *name == color) {
+        return Ok(*color);
+    }
+
+    let parsed = match color.strip_prefix('#') {
+        Some(hex) if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+            u32::from_str_radix(hex, 16)
+                .ok()
+                .map(|rgb| Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8))
+        }
+        Some(_) => None,
+        None => color.parse::<u8>().ok().map(Color::Indexed),
+    };
+
+    parsed.ok_or(DotViewerError::ConfigError(format!("style {spec:?}: invalid color {color}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_colors() {
+        assert_eq!(parse_color("fg", "#ff8000").ok(), Some(Color::Rgb(255, 128, 0)));
+        assert_eq!(parse_color("fg", "208").ok(), Some(Color::Indexed(208)));
+        assert!(parse_color("fg", "#+12345").is_err());
+        assert!(parse_color("fg", "#-12345").is_err());
+        assert!(parse_color("fg", "#ff80").is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::viewer::{
+    config::ClipboardConfig,
+    error::{DotViewerError, DotViewerResult},
+    utils::Task,
+};
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Maximum length of OSC 52 payloads, beyond which terminals commonly drop the sequence
+const OSC52_LIMIT: usize = 100_000;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// `Clipboard` copies texts to the system clipboard.
+///
+/// Texts are copied by OSC 52 escape sequences written to the terminal, which work over SSH,
+/// or piped to a configured command, or written to a configured file as a fallback.
+pub(crate) struct Clipboard {
+    osc52: bool,
+    command: Option<String>,
+    file: Option<String>,
+    /// OSC 52 sequence waiting to be written to the terminal
+    pub pending: Option<String>,
+}
+
+impl Clipboard {
+    pub fn new(config: &ClipboardConfig) -> Self {
+        Self {
+            osc52: config.osc52,
+            command: config.command.clone(),
+            file: config.file.clone(),
+            pending: None,
+        }
+    }
+
+    /// Copy `text` by a task returning how it was copied, to be run on a background job
+    /// as the clipboard command may block, e.g., waiting for a password.
+    pub fn copy(&mut self, text: String) -> DotViewerResult<Task<String>> {
+        let payload = base64(text.as_bytes());
+
+        if self.osc52 && payload.len() <= OSC52_LIMIT {
+            self.pending = Some(osc52(&payload, env::var_os("TMUX").is_some()));
+            return Ok(Box::new(|_| Ok("the clipboard".to_string())));
+        }
+
+        if let Some(command) = self.command.clone() {
+            return Ok(Box::new(move |_| {
+                pipe(&command, &text)?;
+                Ok(format!("`{command}`"))
+            }));
+        }
+
+        if let Some(file) = self.file.clone() {
+            return Ok(Box::new(move |_| {
+                fs::write(&file, text)?;
+                Ok(file)
+            }));
+        }
+
+        let msg = if self.osc52 {
+            format!("{} bytes are too long for OSC 52, with no fallback configured", text.len())
+        } else {
+            "no clipboard configured".to_string()
+        };
+        Err(DotViewerError::ViewerError(msg))
+    }
+}
+
+/// OSC 52 sequence setting the clipboard to the base64 `payload`,
+/// passed through to the outer terminal if running inside tmux.
+fn osc52(payload: &str, tmux: bool) -> String {
+    let osc = format!("\x1b]52;c;{payload}\x07");
+
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", osc.replace('\x1b', "\x1b\x1b"))
+    } else {
+        osc
+    }
+}
+
+fn pipe(command: &str, text: &str) -> DotViewerResult<()> {
+    let mut child = (Command::new("sh").arg("-c").arg(command))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(DotViewerError::ViewerError(format!("`{command}` failed with {status}")));
+    }
+
+    Ok(())
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len() / 3 * 4 + 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_base64_tails() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xff, 0xfe, 0xfd]), "//79");
+    }
+
+    #[test]
+    fn wrap_osc52_in_tmux() {
+        assert_eq!(osc52("Zm9v", false), "\x1b]52;c;Zm9v\x07");
+        assert_eq!(osc52("Zm9v", true), "\x1bPtmux;\x1b\x1b]52;c;Zm9v\x07\x1b\\");
+    }
+}
@@ -0,0 +1,353 @@
+use std::env;
+
+use graphviz_rs::prelude::*;
+use tui::style::{Color, Modifier, Style};
+
+/// X11 colors that dot uses by default, with their `#rrggbb` values.
+/// Numbered variants like `steelblue3` are derived from these, and `grayN` from the percentage.
+const X11: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0xbebebe),
+    ("green", 0x00ff00),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0xbebebe),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrod", 0xeedd82),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslateblue", 0x8470ff),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0xb03060),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("navyblue", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0xa020f0),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("violetred", 0xd02090),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+/// Brightness of the numbered variants `1` to `4` of X11 colors, in percentage.
+const VARIANTS: [u32; 4] = [100, 93, 80, 55];
+
+/// `Paint` is the color of a node given in dot,
+/// its `fillcolor` (or `color`) if its `style` is `filled`, or its `color` otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Paint {
+    /// Color as given in dot, e.g., `red` or `#ff0000`
+    pub spec: String,
+    /// Whether the node is filled with the color
+    pub filled: bool,
+    /// Color in the terminal
+    pub color: Color,
+    /// Modifiers following the `style` of the node, e.g., `bold`
+    pub modifier: Modifier,
+}
+
+impl Paint {
+    /// Paint of `node`, or `None` if it is not colored.
+    /// Colors are in 24-bit if `truecolor`, or in the nearest of 256 colors otherwise.
+    pub fn new(node: &Node, truecolor: bool) -> Option<Self> {
+        let attr = |key: &str| node.attrs().get(key).map(|attr| attr.value().to_lowercase());
+
+        let style = attr("style").unwrap_or_default();
+        let styles: Vec<&str> = style.split(',').map(str::trim).collect();
+
+        let filled = styles.contains(&"filled");
+        let spec = match filled {
+            true => attr("fillcolor").or_else(|| attr("color")),
+            false => attr("color"),
+        }?;
+
+        let (r, g, b) = parse_color(&spec)?;
+        // black is the default outline, which is not a color of interest
+        if !filled && (r, g, b) == (0, 0, 0) {
+            return None;
+        }
+        let color = if truecolor { Color::Rgb(r, g, b) } else { Color::Indexed(nearest(r, g, b)) };
+
+        let modifier = (styles.iter()).fold(Modifier::empty(), |modifier, style| match *style {
+            "bold" => modifier | Modifier::BOLD,
+            "dashed" | "dotted" => modifier | Modifier::ITALIC,
+            "invis" => modifier | Modifier::DIM,
+            _ => modifier,
+        });
+
+        Some(Self { spec, filled, color, modifier })
+    }
+
+    /// Style of items of the node, on the background of the color if filled,
+    /// with black or white texts whichever is more readable.
+    pub fn style(&self) -> Style {
+        let style = Style::default().add_modifier(self.modifier);
+
+        if self.filled {
+            let (r, g, b) = parse_color(&self.spec).unwrap_or((0, 0, 0));
+            let luma = 299 * r as u32 + 587 * g as u32 + 114 * b as u32;
+            let fg = if luma > 128_000 { Color::Black } else { Color::White };
+
+            style.fg(fg).bg(self.color)
+        } else {
+            style.fg(self.color)
+        }
+    }
+}
+
+/// Whether the terminal supports 24-bit colors, as advertised in `COLORTERM`.
+pub(crate) fn truecolor() -> bool {
+    matches!(env::var("COLORTERM").as_deref(), Ok("truecolor" | "24bit"))
+}
+
+/// Parse a dot color, which is an X11 color name, `#rrggbb[aa]` or HSV as `h,s,v`,
+/// taking the first color of a color list like `red:blue`.
+fn parse_color(spec: &str) -> Option<(u8, u8, u8)> {
+    let spec = spec.split(':').next()?;
+    let spec = spec.split(';').next()?.trim();
+    let spec = spec.strip_prefix("/x11/").unwrap_or(spec);
+
+    if let Some(hex) = spec.strip_prefix('#') {
+        if !matches!(hex.len(), 6 | 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let rgb = u32::from_str_radix(hex.get(..6)?, 16).ok()?;
+        return Some(split(rgb));
+    }
+
+    let hsv: Vec<f64> = (spec.split(|c: char| c == ',' || c.is_whitespace()))
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().ok())
+        .collect::<Option<_>>()
+        .unwrap_or_default();
+    if hsv.len() == 3 {
+        return Some(from_hsv(hsv[0], hsv[1], hsv[2]));
+    }
+
+    parse_name(spec)
+}
+
+fn parse_name(name: &str) -> Option<(u8, u8, u8)> {
+    if let Some((_, rgb)) = X11.iter().find(|(n, _)| *n == name) {
+        return Some(split(*rgb));
+    }
+
+    let base = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number: u32 = name[base.len()..].parse().ok()?;
+
+    if base == "gray" || base == "grey" {
+        let level = (number.min(100) * 255 + 50) / 100;
+        return Some((level as u8, level as u8, level as u8));
+    }
+
+    let percent = *VARIANTS.get((number as usize).checked_sub(1)?)?;
+    let (_, rgb) = X11.iter().find(|(n, _)| *n == base)?;
+    let (r, g, b) = split(*rgb);
+    let scale = |c: u8| (c as u32 * percent / 100) as u8;
+
+    Some((scale(r), scale(g), scale(b)))
+}
+
+fn split(rgb: u32) -> (u8, u8, u8) {
+    ((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+}
+
+fn from_hsv(h: f64, s: f64, v: f64) -> (u8, u8, u8) {
+    let h = (h.clamp(0.0, 1.0) * 6.0) % 6.0;
+    let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+
+    let c = v * s;
+    let x = c * (1.0 - ((h % 2.0) - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+    let byte = |c: f64| ((c + m) * 255.0).round() as u8;
+
+    (byte(r), byte(g), byte(b))
+}
+
+/// Index of the nearest color in the 6x6x6 color cube or the grayscale ramp of 256 colors.
+fn nearest(r: u8, g: u8, b: u8) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    let level = |c: u8| (0..6).min_by_key(|&i| (LEVELS[i] as i32 - c as i32).abs()).unwrap();
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+
+    let gray = ((r as u32 + g as u32 + b as u32) / 3) as u8;
+    let step = ((gray.saturating_sub(8) as u32 + 5) / 10).min(23) as u8;
+    let ramp = 8 + 10 * step;
+
+    let distance = |(cr, cg, cb): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(cr, r) + d(cg, g) + d(cb, b)
+    };
+
+    if distance((ramp, ramp, ramp)) < distance(cube) {
+        232 + step
+    } else {
+        16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hex_colors() {
+        assert_eq!(parse_color("#ff8000"), Some((255, 128, 0)));
+        assert_eq!(parse_color("#ff800080"), Some((255, 128, 0)));
+        assert_eq!(parse_color("#ff80"), None);
+        assert_eq!(parse_color("#+f8000"), None);
+        assert_eq!(parse_color("#aaaaaéb"), None);
+        assert_eq!(parse_color("#ééé"), None);
+    }
+
+    #[test]
+    fn parse_x11_names() {
+        assert_eq!(parse_color("gray"), Some((190, 190, 190)));
+        assert_eq!(parse_color("grey"), Some((190, 190, 190)));
+        assert_eq!(parse_color("silver"), Some((192, 192, 192)));
+        assert_eq!(parse_color("gray100"), Some((255, 255, 255)));
+        assert_eq!(parse_color("/x11/maroon"), Some((176, 48, 96)));
+    }
+}
@@ -0,0 +1,200 @@
+use crate::viewer::error::{DotViewerError, DotViewerResult};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use regex::Regex;
+
+/// `Fields` extracts named numeric fields, like `D` in `(H: 4, D: 128)`, from nodes.
+///
+/// Fields configured by users are extracted with the first capture group of their regex.
+/// Any other field `name` is extracted with the default pattern, `name: value` or `name=value`.
+pub(crate) struct Fields {
+    regexes: HashMap<String, Regex>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl Fields {
+    pub fn new(fields: &HashMap<String, String>) -> DotViewerResult<Self> {
+        let mut regexes = HashMap::new();
+
+        for (name, regex) in fields {
+            let regex = Regex::new(regex)
+                .map_err(|e| DotViewerError::ConfigError(format!("field {name}: {e}")))?;
+            if regex.captures_len() < 2 {
+                let msg = format!("field {name}: regex should have a capture group");
+                return Err(DotViewerError::ConfigError(msg));
+            }
+
+            regexes.insert(name.clone(), regex);
+        }
+
+        Ok(Self { regexes })
+    }
+
+    /// Get the regex extracting the field `name`.
+    pub fn regex(&self, name: &str) -> Regex {
+        self.regexes.get(name).cloned().unwrap_or_else(|| {
+            let name = regex::escape(name);
+            let pattern = format!(r"\b{name}\s*[:=]\s*(-?\d+(?:\.\d+)?\s*(?:[KMGT]i?)?B?)\b");
+
+            Regex::new(&pattern).expect("default field pattern should be valid")
+        })
+    }
+}
+
+/// Extract the first value matching `regex` in `text`.
+pub(crate) fn extract_field(regex: &Regex, text: &str) -> Option<f64> {
+    regex.captures_iter(text).find_map(|caps| caps.get(1).and_then(|m| parse_number(m.as_str())))
+}
+
+/// Unit suffixes with their multipliers, those with `i` being powers of 1024
+/// and the others powers of 1000.
+const UNITS: &[(&str, f64)] = &[
+    ("Ki", 1024_f64),
+    ("Mi", 1048576_f64),
+    ("Gi", 1073741824_f64),
+    ("Ti", 1099511627776_f64),
+    ("K", 1e3),
+    ("M", 1e6),
+    ("G", 1e9),
+    ("T", 1e12),
+];
+
+/// Parse a number with an optional unit suffix, e.g., `128`, `1.5K`, `4MiB`,
+/// rejecting non-finite numbers, e.g., `NaN` and `inf`, which compare unlike numbers.
+pub(crate) fn parse_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let value = value.strip_suffix('B').unwrap_or(value);
+
+    let (value, unit) = (UNITS.iter())
+        .find_map(|(suffix, unit)| value.strip_suffix(suffix).map(|value| (value, *unit)))
+        .unwrap_or((value, 1_f64));
+
+    (value.trim().parse::<f64>().ok()).map(|value| value * unit).filter(|value| value.is_finite())
+}
+
+impl Op {
+    pub fn apply(&self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            Self::Lt => lhs < rhs,
+            Self::Le => lhs <= rhs,
+            Self::Gt => lhs > rhs,
+            Self::Ge => lhs >= rhs,
+            Self::Eq => lhs == rhs,
+            Self::Ne => lhs != rhs,
+        }
+    }
+}
+
+impl FromStr for Op {
+    type Err = DotViewerError;
+
+    fn from_str(op: &str) -> Result<Self, Self::Err> {
+        match op {
+            "<" => Ok(Self::Lt),
+            "<=" => Ok(Self::Le),
+            ">" => Ok(Self::Gt),
+            ">=" => Ok(Self::Ge),
+            "=" | "==" => Ok(Self::Eq),
+            "!=" => Ok(Self::Ne),
+            _ => Err(DotViewerError::CommandError(format!("no such operator {op}"))),
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op = match self {
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+        };
+
+        write!(f, "{op}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_plain_numbers() {
+        assert_eq!(parse_number("128"), Some(128_f64));
+        assert_eq!(parse_number(" -1.5 "), Some(-1.5));
+        assert_eq!(parse_number("64B"), Some(64_f64));
+        assert_eq!(parse_number(""), None);
+        assert_eq!(parse_number("abc"), None);
+    }
+
+    #[test]
+    fn parse_units() {
+        assert_eq!(parse_number("1.5K"), Some(1500_f64));
+        assert_eq!(parse_number("2 M"), Some(2e6));
+        assert_eq!(parse_number("3GB"), Some(3e9));
+        assert_eq!(parse_number("1T"), Some(1e12));
+        assert_eq!(parse_number("4Ki"), Some(4096_f64));
+        assert_eq!(parse_number("4MiB"), Some(4_f64 * 1024_f64 * 1024_f64));
+        assert_eq!(parse_number("1GiB"), Some(1073741824_f64));
+        assert_eq!(parse_number("1 TiB"), Some(1099511627776_f64));
+    }
+
+    #[test]
+    fn reject_non_finite_numbers() {
+        assert_eq!(parse_number("NaN"), None);
+        assert_eq!(parse_number("inf"), None);
+        assert_eq!(parse_number("-infinity"), None);
+        assert_eq!(parse_number("1e308T"), None);
+    }
+
+    #[test]
+    fn reject_stray_i() {
+        assert_eq!(parse_number("4i"), None);
+        assert_eq!(parse_number("4iB"), None);
+        assert_eq!(parse_number("4Bi"), None);
+        assert_eq!(parse_number("4KiKi"), None);
+    }
+
+    #[test]
+    fn extract_default_fields() {
+        let fields = Fields::new(&HashMap::new()).unwrap();
+        let regex = fields.regex("D");
+
+        assert_eq!(extract_field(&regex, "(H: 4, D: 128)"), Some(128_f64));
+        assert_eq!(extract_field(&regex, "D=4MiB"), Some(4194304_f64));
+        assert_eq!(extract_field(&regex, "ID: 3"), None);
+    }
+
+    #[test]
+    fn apply_ops() {
+        let ops = ["<", "<=", ">", ">=", "==", "!="].map(|op| op.parse::<Op>().unwrap());
+        let expected = [
+            (1_f64, 2_f64, [true, true, false, false, false, true]),
+            (2_f64, 2_f64, [false, true, false, true, true, false]),
+            (3_f64, 2_f64, [false, false, true, true, false, true]),
+        ];
+
+        for (lhs, rhs, results) in expected {
+            for (op, result) in ops.iter().zip(results) {
+                assert_eq!(op.apply(lhs, rhs), result, "{lhs} {op} {rhs}");
+            }
+        }
+
+        assert_eq!("=".parse::<Op>().unwrap(), Op::Eq);
+        assert!("=<".parse::<Op>().is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use html_parser::{Dom, Element, Node};
+
+/// Maximum `rowspan` and `colspan` of a cell, as in Graphviz.
+const MAX_SPAN: usize = 65535;
+
+pub fn parse(html: &str) -> Vec<String> {
+    let dom = Dom::parse(html);
+
+    dom.map(parse_dom).unwrap_or_default()
+}
+
+fn parse_dom(dom: Dom) -> Vec<String> {
+    let mut texts = Vec::new();
+
+    for node in &dom.children {
+        parse_node(node, &mut texts);
+    }
+
+    texts
+}
+
+fn parse_element(element: &Element, texts: &mut Vec<String>) {
+    for node in &element.children {
+        parse_node(node, texts);
+    }
+}
+
+fn parse_node(node: &Node, texts: &mut Vec<String>) {
+    match &node {
+        Node::Element(element) => parse_element(element, texts),
+        Node::Text(text) => texts.push(text.clone()),
+        _ => {}
+    }
+}
+
+/// A cell of an HTML-like table label.
+pub struct Cell {
+    pub text: String,
+    pub port: Option<String>,
+    pub colspan: usize,
+    pub rowspan: usize,
+}
+
+/// Parse the outermost table of an HTML-like label into rows of cells,
+/// or `None` if there is no table.
+pub fn parse_table(html: &str) -> Option<Vec<Vec<Cell>>> {
+    let dom = Dom::parse(html).ok()?;
+    let table = dom.children.iter().find_map(find_table)?;
+
+    let rows = children(table, "tr").map(|tr| children(tr, "td").map(parse_cell).collect());
+
+    Some(rows.collect())
+}
+
+/// Lay out `rows` of cells into a grid, where a cell is put at its top-left slot,
+/// leaving the other slots it spans empty. Spans are capped at `MAX_SPAN`.
+pub fn grid(rows: &[Vec<Cell>]) -> Vec<Vec<Option<&Cell>>> {
+    let mut grid: Vec<Vec<Option<&Cell>>> = Vec::new();
+    let mut taken: Vec<Vec<bool>> = Vec::new();
+
+    for (r, row) in rows.iter().enumerate() {
+        let mut c = 0;
+
+        for cell in row {
+            while taken.get(r).and_then(|row| row.get(c)) == Some(&true) {
+                c += 1;
+            }
+
+            let (rowspan, colspan) =
+                (cell.rowspan.clamp(1, MAX_SPAN), cell.colspan.clamp(1, MAX_SPAN));
+            for dr in 0..rowspan {
+                for dc in 0..colspan {
+                    take(&mut grid, &mut taken, r + dr, c + dc);
+                }
+            }
+            grid[r][c] = Some(cell);
+
+            c += colspan;
+        }
+    }
+
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    grid.iter_mut().for_each(|row| row.resize(width, None));
+
+    grid
+}
+
+fn take(grid: &mut Vec<Vec<Option<&Cell>>>, taken: &mut Vec<Vec<bool>>, r: usize, c: usize) {
+    if grid.len() <= r {
+        grid.resize_with(r + 1, Vec::new);
+        taken.resize_with(r + 1, Vec::new);
+    }
+    if grid[r].len() <= c {
+        grid[r].resize(c + 1, None);
+        taken[r].resize(c + 1, false);
+    }
+
+    taken[r][c] = true;
+}
+
+fn find_table(node: &Node) -> Option<&Element> {
+    match node {
+        Node::Element(element) if is(element, "table") => Some(element),
+        Node::Element(element) => element.children.iter().find_map(find_table),
+        _ => None,
+    }
+}
+
+fn children<'a>(element: &'a Element, name: &'a str) -> impl Iterator<Item = &'a Element> {
+    element.children.iter().filter_map(move |node| match node {
+        Node::Element(element) if is(element, name) => Some(element),
+        _ => None,
+    })
+}
+
+fn parse_cell(element: &Element) -> Cell {
+    let mut texts = Vec::new();
+    parse_element(element, &mut texts);
+
+    let texts: Vec<&str> = texts.iter().map(|text| text.trim()).filter(|t| !t.is_empty()).collect();
+    let text = texts.join(" ");
+
+    let attr = |name: &str| {
+        (element.attributes.iter())
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .and_then(|(_, value)| value.clone())
+    };
+    let span = |name: &str| attr(name).and_then(|value| value.parse().ok()).unwrap_or(1).max(1);
+
+    Cell { text, port: attr("port"), colspan: span("colspan"), rowspan: span("rowspan") }
+}
+
+fn is(element: &Element, name: &str) -> bool {
+    element.name.eq_ignore_ascii_case(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(text: &str, rowspan: usize, colspan: usize) -> Cell {
+        Cell { text: text.to_string(), port: None, colspan, rowspan }
+    }
+
+    fn texts<'a>(grid: &[Vec<Option<&'a Cell>>]) -> Vec<Vec<&'a str>> {
+        let text = |cell: &Option<&'a Cell>| cell.map_or("", |cell| cell.text.as_str());
+        grid.iter().map(|row| row.iter().map(text).collect()).collect()
+    }
+
+    #[test]
+    fn parse_tables() {
+        let html = r#"<b><table><tr><td port="in">a</td><td COLSPAN="2"> b <i>c</i></td></tr>
+            <tr><td rowspan="x">d</td><td colspan="0">e</td></tr></table></b>"#;
+        let rows = parse_table(html).unwrap();
+
+        let cells: Vec<_> = (rows.iter().flatten())
+            .map(|c| (c.text.as_str(), c.port.as_deref(), c.rowspan, c.colspan))
+            .collect();
+        assert_eq!(rows.iter().map(Vec::len).collect::<Vec<_>>(), [2, 2]);
+        assert_eq!(
+            cells,
+            [("a", Some("in"), 1, 1), ("b c", None, 1, 2), ("d", None, 1, 1), ("e", None, 1, 1)]
+        );
+
+        assert!(parse_table("<b>no table</b>").is_none());
+    }
+
+    #[test]
+    fn grid_spans() {
+        // a | b b
+        // a | c d
+        let rows =
+            vec![vec![cell("a", 2, 1), cell("b", 1, 2)], vec![cell("c", 1, 1), cell("d", 1, 1)]];
+
+        assert_eq!(texts(&grid(&rows)), [["a", "b", ""], ["", "c", "d"]]);
+    }
+
+    #[test]
+    fn grid_ragged_rows() {
+        let rows = vec![vec![cell("a", 1, 1)], vec![cell("b", 1, 1), cell("c", 2, 1)]];
+
+        assert_eq!(texts(&grid(&rows)), [["a", ""], ["b", "c"], ["", ""]]);
+    }
+
+    #[test]
+    fn grid_caps_spans() {
+        let rows = vec![vec![cell("a", 1, usize::MAX), cell("b", 1, 1)]];
+        let grid = grid(&rows);
+
+        assert_eq!(grid[0].len(), MAX_SPAN + 1);
+        assert_eq!(grid[0][MAX_SPAN].map(|cell| cell.text.as_str()), Some("b"));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::viewer::error::{DotViewerError, DotViewerResult};
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Frames of the spinner shown while a job is running
+const SPINNER: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Task run by a background job, reporting its progress and checking for cancellation
+pub(crate) type Task<T> = Box<dyn FnOnce(&Progress) -> DotViewerResult<T> + Send>;
+
+/// `Job` runs a task on a background thread, keeping the user interface responsive meanwhile.
+pub(crate) struct Job<T> {
+    /// Name of the job shown in the status line, e.g., `neighbors 20`
+    pub name: String,
+    started: Instant,
+    progress: Arc<Progress>,
+    receiver: Receiver<DotViewerResult<T>>,
+}
+
+/// `Progress` is shared between a job and its task, to report progress and to cancel the task.
+#[derive(Default)]
+pub(crate) struct Progress {
+    done: AtomicUsize,
+    total: AtomicUsize,
+    cancelled: AtomicBool,
+}
+
+impl<T: Send + 'static> Job<T> {
+    pub fn spawn(name: String, task: Task<T>) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let progress = Arc::new(Progress::default());
+
+        let shared = progress.clone();
+        thread::spawn(move || {
+            // the receiver is gone if the job was cancelled
+            let _ = sender.send(task(&shared));
+        });
+
+        Self { name, started: Instant::now(), progress, receiver }
+    }
+}
+
+impl<T> Job<T> {
+    /// Result of the task if it is done, without blocking.
+    pub fn poll(&self) -> Option<DotViewerResult<T>> {
+        self.wait(Duration::ZERO)
+    }
+
+    /// Result of the task if it is done within `timeout`.
+    pub fn wait(&self, timeout: Duration) -> Option<DotViewerResult<T>> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(res) => Some(res),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => Some(Err(self.failure())),
+        }
+    }
+
+    /// Result of the task, blocking until it is done.
+    pub fn join(self) -> DotViewerResult<T> {
+        self.receiver.recv().unwrap_or_else(|_| Err(self.failure()))
+    }
+
+    /// Cancel the task, dropping its result, and return the name of the job.
+    pub fn cancel(self) -> String {
+        self.progress.cancelled.store(true, Ordering::Relaxed);
+
+        self.name
+    }
+
+    /// Status of the job, e.g., `⠹ search foo 42% 3s`.
+    pub fn status(&self) -> String {
+        let elapsed = self.started.elapsed();
+        let frame = SPINNER[(elapsed.as_millis() / 100) as usize % SPINNER.len()];
+
+        let mut status = format!("{frame} {}", self.name);
+
+        let total = self.progress.total.load(Ordering::Relaxed);
+        if total > 0 {
+            let done = self.progress.done.load(Ordering::Relaxed).min(total);
+            status.push_str(&format!(" {}%", done * 100 / total));
+        }
+
+        if elapsed.as_secs() > 0 {
+            status.push_str(&format!(" {}s", elapsed.as_secs()));
+        }
+
+        status
+    }
+
+    fn failure(&self) -> DotViewerError {
+        DotViewerError::ViewerError(format!("{} failed", self.name))
+    }
+}
+
+impl Progress {
+    /// Set the number of steps of the task.
+    pub fn total(&self, total: usize) {
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    /// Count a step of the task done.
+    pub fn advance(&self) {
+        self.done.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Fail if the task is cancelled, to stop it between steps.
+    pub fn check(&self) -> DotViewerResult<()> {
+        if self.cancelled() {
+            return Err(DotViewerError::ViewerError("cancelled".to_string()));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+/// `Jumps` holds the history of navigations in a view, as Vim's jump list.
+///
+/// Jumping back and forth moves the cursor in the history without modifying it,
+/// while recording a new jump drops the entries after the cursor.
+#[derive(Default)]
+pub(crate) struct Jumps {
+    pub items: Vec<String>,
+    pub cursor: usize,
+}
+
+impl Jumps {
+    /// Record a jump to `id`, ignoring consecutive duplicates.
+    pub fn record(&mut self, id: &str) {
+        if !self.items.is_empty() {
+            self.items.truncate(self.cursor + 1);
+        }
+
+        if self.items.last().map(String::as_str) != Some(id) {
+            self.items.push(id.to_string());
+        }
+
+        self.cursor = self.items.len() - 1;
+    }
+
+    /// Move back in the history from the node `current`, returning the id to jump to.
+    /// As in Vim, `current` is recorded first if it moved away from the newest jump,
+    /// such that moving forward returns to it.
+    pub fn back(&mut self, current: &str) -> Option<String> {
+        let newest = self.cursor + 1 == self.items.len();
+        if newest && self.items[self.cursor] != current {
+            self.items.push(current.to_string());
+            self.cursor += 1;
+        }
+
+        (self.cursor > 0).then(|| {
+            self.cursor -= 1;
+            self.items[self.cursor].clone()
+        })
+    }
+
+    /// Move forward in the history, returning the id to jump to.
+    pub fn forward(&mut self) -> Option<String> {
+        (self.cursor + 1 < self.items.len()).then(|| {
+            self.cursor += 1;
+            self.items[self.cursor].clone()
+        })
+    }
+
+    /// The id visited right before the current one.
+    pub fn last(&self) -> Option<String> {
+        (self.cursor > 0).then(|| self.items[self.cursor - 1].clone())
+    }
+
+    /// At most `n` recent jumps up to the cursor, in order of visit.
+    pub fn recent(&self, n: usize) -> &[String] {
+        if self.items.is_empty() {
+            return &[];
+        }
+
+        let end = self.cursor + 1;
+        let start = end.saturating_sub(n);
+
+        &self.items[start..end]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jump_back_and_forth() {
+        let mut jumps = Jumps::default();
+        jumps.record("a");
+        jumps.record("b");
+        jumps.record("b");
+
+        assert_eq!(jumps.items, vec!["a", "b"]);
+        assert_eq!(jumps.back("b"), Some("a".to_string()));
+        assert_eq!(jumps.back("a"), None);
+        assert_eq!(jumps.forward(), Some("b".to_string()));
+        assert_eq!(jumps.forward(), None);
+    }
+
+    #[test]
+    fn record_current_on_back() {
+        let mut jumps = Jumps::default();
+        jumps.record("a");
+        jumps.record("b");
+
+        // moved from b to c without jumping
+        assert_eq!(jumps.back("c"), Some("b".to_string()));
+        assert_eq!(jumps.forward(), Some("c".to_string()));
+        assert_eq!(jumps.items, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn drop_jumps_after_cursor() {
+        let mut jumps = Jumps::default();
+        jumps.record("a");
+        jumps.record("b");
+        jumps.back("b");
+        jumps.record("c");
+
+        assert_eq!(jumps.items, vec!["a", "c"]);
+        assert_eq!(jumps.recent(1), &["c".to_string()]);
+        assert_eq!(jumps.last(), Some("a".to_string()));
+    }
+
+    #[test]
+    fn back_without_jumps() {
+        let mut jumps = Jumps::default();
+
+        assert_eq!(jumps.back("a"), None);
+        assert!(jumps.items.is_empty());
+    }
+}
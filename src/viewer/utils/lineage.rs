@@ -0,0 +1,168 @@
+use crate::viewer::{modes::SearchMode, utils::List, utils::Op};
+
+use std::fmt;
+
+/// `Origin` records the tab (view) that a view was derived from, and how.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Origin {
+    /// Id of the parent view
+    pub parent: usize,
+    pub derivation: Derivation,
+}
+
+/// Operations deriving a view from its parent, with their arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Derivation {
+    Filter { smode: SearchMode, key: String },
+    Where { field: String, op: Op, value: String },
+    Subgraph { key: String },
+    Neighbors { id: String, depth: usize },
+    Duplicate,
+}
+
+/// `Lineage` holds the derivation tree of tabs (views), flattened in depth-first order.
+#[derive(Default)]
+pub(crate) struct Lineage {
+    /// Indices of tabs with their depths in the tree
+    pub list: List<(usize, usize)>,
+}
+
+impl fmt::Display for Derivation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Filter { smode: SearchMode::Fuzzy, key } => write!(f, "filter /{key}"),
+            Self::Filter { smode: SearchMode::Regex, key } => write!(f, "filter r{key}"),
+            Self::Subgraph { key } => write!(f, "subgraph {key}"),
+            Self::Neighbors { id, depth } => write!(f, "neighbors {depth} of {id}"),
+            _ => write!(f, "{}", self.command()),
+        }
+    }
+}
+
+impl Derivation {
+    /// Command deriving the view again, to be edited before running.
+    pub fn command(&self) -> String {
+        match self {
+            Self::Filter { .. } => "filter".to_string(),
+            Self::Where { field, op, value } => format!("where {field} {op} {value}"),
+            Self::Subgraph { .. } => "subgraph".to_string(),
+            Self::Neighbors { depth, .. } => format!("neighbors {depth}"),
+            Self::Duplicate => "dup".to_string(),
+        }
+    }
+}
+
+impl Lineage {
+    /// Build the tree from `links` of tabs, each an id and the id of its parent.
+    /// Tabs whose parents are closed are shown as roots.
+    pub fn new(links: &[(usize, Option<usize>)]) -> Self {
+        let ids: Vec<usize> = links.iter().map(|(id, _)| *id).collect();
+        let is_root = |parent: &Option<usize>| match parent {
+            Some(parent) => !ids.contains(parent),
+            None => true,
+        };
+
+        let mut rows = Vec::new();
+        for (idx, (_, parent)) in links.iter().enumerate() {
+            if is_root(parent) {
+                walk(links, idx, 0, &mut rows);
+            }
+        }
+
+        Self { list: List::from_iter(rows) }
+    }
+
+    /// Index of the selected tab.
+    pub fn selected(&self) -> Option<usize> {
+        self.list.selected().map(|(idx, _)| idx)
+    }
+
+    /// Select the row of the tab at `idx`.
+    pub fn select(&mut self, idx: usize) {
+        if let Some(row) = self.list.items.iter().position(|&(i, _)| i == idx) {
+            self.list.select(row);
+        }
+    }
+
+    /// Indices of the selected tab and all tabs derived from it, in descending order.
+    pub fn branch(&self) -> Vec<usize> {
+        let row = match self.list.state.selected() {
+            Some(row) => row,
+            None => return Vec::new(),
+        };
+
+        let (_, depth) = self.list.items[row];
+        let descendants = self.list.items[row + 1..].iter().take_while(|&&(_, d)| d > depth);
+
+        let mut branch: Vec<usize> =
+            std::iter::once(&self.list.items[row]).chain(descendants).map(|&(i, _)| i).collect();
+        branch.sort_unstable_by(|a, b| b.cmp(a));
+
+        branch
+    }
+}
+
+fn walk(
+    links: &[(usize, Option<usize>)],
+    idx: usize,
+    depth: usize,
+    rows: &mut Vec<(usize, usize)>,
+) {
+    rows.push((idx, depth));
+
+    let id = links[idx].0;
+    for (child, (_, parent)) in links.iter().enumerate() {
+        if *parent == Some(id) {
+            walk(links, child, depth + 1, rows);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 ─┬─ 1 ─── 3
+    //    └─ 2
+    // 4 (parent 9 closed)
+    const LINKS: &[(usize, Option<usize>)] =
+        &[(0, None), (1, Some(0)), (2, Some(0)), (3, Some(1)), (4, Some(9))];
+
+    #[test]
+    fn flatten_depth_first() {
+        let lineage = Lineage::new(LINKS);
+
+        assert_eq!(lineage.list.items, [(0, 0), (1, 1), (3, 2), (2, 1), (4, 0)]);
+    }
+
+    #[test]
+    fn branch_descendants() {
+        let mut lineage = Lineage::new(LINKS);
+        let mut branch = |idx: usize| {
+            lineage.select(idx);
+            lineage.branch()
+        };
+
+        assert_eq!(branch(0), [3, 2, 1, 0]);
+        assert_eq!(branch(1), [3, 1]);
+        assert_eq!(branch(2), [2]);
+        assert_eq!(branch(3), [3]);
+        assert_eq!(branch(4), [4]);
+    }
+
+    #[test]
+    fn branch_without_selection() {
+        assert!(Lineage::default().branch().is_empty());
+    }
+
+    #[test]
+    fn show_derivations() {
+        let filter = Derivation::Filter { smode: SearchMode::Regex, key: "^a".to_string() };
+        let neighbors = Derivation::Neighbors { id: "a".to_string(), depth: 2 };
+
+        assert_eq!(filter.to_string(), "filter r^a");
+        assert_eq!(neighbors.to_string(), "neighbors 2 of a");
+        assert_eq!(neighbors.command(), "neighbors 2");
+        assert_eq!(Derivation::Duplicate.to_string(), "dup");
+    }
+}
@@ -1,11 +1,20 @@
 #![allow(dead_code)]
 
-use tui::widgets::ListState;
+use tui::{layout::Rect, widgets::ListState};
 
 // https://github.com/fdehau/tui-rs/blob/master/examples/list.rs
+#[derive(Default)]
 pub(crate) struct List<T> {
     pub state: ListState,
     pub items: Vec<T>,
+    /// Area where the items were rendered in the last frame
+    pub area: Rect,
+    /// Index of the first item visible in the last rendered frame
+    pub offset: usize,
+    /// Number of items visible in the last rendered frame
+    pub height: usize,
+    /// Number of characters scrolled horizontally, hiding the beginning of items
+    pub shift: usize,
 }
 
 impl<T: Clone + Eq> std::iter::FromIterator<T> for List<T> {
@@ -14,7 +23,12 @@ impl<T: Clone + Eq> std::iter::FromIterator<T> for List<T> {
 
         let items = Vec::from_iter(iter);
 
-        let mut list = Self { state, items };
+        let area = Rect::default();
+        let offset = 0;
+        let height = 0;
+        let shift = 0;
+
+        let mut list = Self { state, items, area, offset, height, shift };
 
         if !list.items.is_empty() {
             list.state.select(Some(0));
@@ -57,6 +71,31 @@ impl<T: Clone + Eq> List<T> {
         }
     }
 
+    /// Move the selection by `offset`, wrapping around for a single step,
+    /// but stopping at the first and the last for counts, as Vim does.
+    pub fn step(&mut self, offset: isize) {
+        match offset {
+            1 => self.next(),
+            -1 => self.previous(),
+            _ => self.scroll(offset),
+        }
+    }
+
+    /// Move the selection by `offset`, stopping at the first and the last.
+    pub fn scroll(&mut self, offset: isize) {
+        if let Some(i) = self.state.selected() {
+            let i = i.saturating_add_signed(offset);
+            self.nth(i);
+        }
+    }
+
+    /// Select the `idx`-th item, or the last if out of range.
+    pub fn nth(&mut self, idx: usize) {
+        if !self.items.is_empty() {
+            self.state.select(Some(idx.min(self.items.len() - 1)));
+        }
+    }
+
     pub fn select(&mut self, idx: usize) {
         if idx < self.items.len() {
             self.state.select(Some(idx));
@@ -70,4 +109,109 @@ impl<T: Clone + Eq> List<T> {
     pub fn find(&self, key: T) -> Option<usize> {
         self.items.iter().position(|item| *item == key)
     }
+
+    /// Record the `area` where the items are rendered, one item per row,
+    /// scrolling as `tui` does such that positions in the area can be mapped to items.
+    pub fn frame(&mut self, area: Rect) {
+        self.area = area;
+        self.height = area.height as usize;
+        self.offset = follow(self.offset, self.state.selected(), self.height, self.items.len());
+    }
+
+    /// Whether the items were rendered over column `x` and row `y`.
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        contains(self.area, x, y)
+    }
+
+    /// Index of the item rendered at column `x` and row `y`.
+    pub fn hit(&self, x: u16, y: u16) -> Option<usize> {
+        (self.contains(x, y))
+            .then(|| self.offset + (y - self.area.y) as usize)
+            .filter(|&idx| idx < self.items.len())
+    }
+}
+
+/// Offset of the first visible item in `tui` lists and tables with items of the same height,
+/// scrolling minimally from the previous `offset` to keep the selected item visible.
+pub(crate) fn follow(offset: usize, selected: Option<usize>, height: usize, len: usize) -> usize {
+    let offset = offset.min(len.saturating_sub(1));
+
+    match selected {
+        None => 0,
+        Some(selected) if height > 0 && selected >= offset + height => selected + 1 - height,
+        Some(selected) if selected < offset => selected,
+        Some(_) => offset,
+    }
+}
+
+pub(crate) fn contains(area: Rect, x: u16, y: u16) -> bool {
+    (area.left()..area.right()).contains(&x) && (area.top()..area.bottom()).contains(&y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(len: usize, selected: usize) -> List<usize> {
+        let mut list = List::from_iter(0..len);
+        list.select(selected);
+        list
+    }
+
+    #[test]
+    fn step_wraps_once() {
+        let mut list = list(3, 2);
+
+        list.step(1);
+        assert_eq!(list.selected(), Some(0));
+        list.step(-1);
+        assert_eq!(list.selected(), Some(2));
+    }
+
+    #[test]
+    fn step_counts_stop_at_ends() {
+        let mut list = list(10, 5);
+
+        list.step(3);
+        assert_eq!(list.selected(), Some(8));
+        list.step(3);
+        assert_eq!(list.selected(), Some(9));
+        list.step(-20);
+        assert_eq!(list.selected(), Some(0));
+    }
+
+    #[test]
+    fn step_empty() {
+        let mut list = list(0, 0);
+
+        list.step(1);
+        list.step(-5);
+        assert_eq!(list.selected(), None);
+    }
+
+    #[test]
+    fn follow_selection() {
+        // no scroll while visible
+        assert_eq!(follow(0, Some(4), 5, 10), 0);
+        assert_eq!(follow(3, Some(3), 5, 10), 3);
+        // scroll minimally below and above
+        assert_eq!(follow(0, Some(5), 5, 10), 1);
+        assert_eq!(follow(3, Some(1), 5, 10), 1);
+        // clamp a stale offset after the items shrunk
+        assert_eq!(follow(8, Some(2), 5, 3), 2);
+        assert_eq!(follow(3, None, 5, 10), 0);
+        assert_eq!(follow(3, Some(7), 0, 10), 3);
+    }
+
+    #[test]
+    fn hit_items() {
+        let mut list = list(10, 7);
+        list.frame(Rect::new(2, 1, 20, 5));
+
+        assert_eq!(list.offset, 3);
+        assert_eq!(list.hit(2, 1), Some(3));
+        assert_eq!(list.hit(21, 5), Some(7));
+        assert_eq!(list.hit(22, 5), None);
+        assert_eq!(list.hit(2, 6), None);
+    }
 }
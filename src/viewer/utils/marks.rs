@@ -0,0 +1,157 @@
+use crate::viewer::{
+    error::{DotViewerError, DotViewerResult},
+    utils::List,
+};
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// `Marks` holds named marks on nodes, as Vim's marks `a` to `z`.
+///
+/// Marks are persisted per dot file in
+/// `$XDG_DATA_HOME/dot-viewer/marks` (or `~/.local/share/dot-viewer/marks`),
+/// in a sidecar file named after the hash of the absolute path to the dot file.
+#[derive(Default)]
+pub(crate) struct Marks {
+    /// Absolute path to the dot file
+    dot: String,
+    /// Path to the sidecar file persisting the marks
+    path: Option<PathBuf>,
+    /// Marks sorted by name
+    pub list: List<(char, String)>,
+}
+
+/// `Sidecar` is the contents of a sidecar file, naming the dot file to tell apart colliding hashes.
+#[derive(Serialize, Deserialize)]
+struct Sidecar<T> {
+    dot: String,
+    marks: BTreeMap<String, T>,
+}
+
+impl Marks {
+    /// Load marks of the dot file in `dot`.
+    /// Returns empty marks if there are no persisted marks.
+    pub fn load(dot: &str) -> DotViewerResult<Self> {
+        let dot = fs::canonicalize(dot).unwrap_or_else(|_| Path::new(dot).to_path_buf());
+        let dot = dot.to_string_lossy().to_string();
+        let path = sidecar_dir().map(|dir| dir.join(sidecar_name(&dot)));
+
+        let mut marks = Self { dot, path, ..Default::default() };
+
+        if let Some(path) = marks.path.as_ref().filter(|path| path.exists()) {
+            let contents = fs::read_to_string(path)?;
+            let saved: Sidecar<String> = toml::from_str(&contents)
+                .map_err(|e| DotViewerError::ConfigError(format!("{}: {e}", path.display())))?;
+            if saved.dot != marks.dot {
+                return Ok(marks);
+            }
+
+            let saved = saved.marks.into_iter().filter_map(|(name, id)| {
+                let mut chars = name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(name), None) if name.is_ascii_lowercase() => Some((name, id)),
+                    _ => None,
+                }
+            });
+            marks.list = List::from_iter(saved);
+        }
+
+        Ok(marks)
+    }
+
+    /// Get the node id marked as `name`.
+    pub fn get(&self, name: char) -> Option<String> {
+        self.list.items.iter().find(|(c, _)| *c == name).map(|(_, id)| id.clone())
+    }
+
+    /// Mark the node `id` as `name`, and persist the marks.
+    pub fn set(&mut self, name: char, id: String) -> DotViewerResult<()> {
+        if !name.is_ascii_lowercase() {
+            return Err(DotViewerError::ViewerError(format!("invalid mark name {name:?}")));
+        }
+
+        let mut items: Vec<(char, String)> =
+            self.list.items.drain(..).filter(|(c, _)| *c != name).collect();
+        items.push((name, id));
+        items.sort_unstable();
+
+        self.list = List::from_iter(items);
+
+        self.save()
+    }
+
+    /// Delete the selected mark, and persist the marks.
+    pub fn delete(&mut self) -> DotViewerResult<()> {
+        let idx = (self.list.state.selected())
+            .ok_or(DotViewerError::ViewerError("no mark selected".to_string()))?;
+
+        self.list.items.remove(idx);
+        if idx >= self.list.items.len() {
+            self.list.last();
+        }
+        if self.list.items.is_empty() {
+            self.list.state.select(None);
+        }
+
+        self.save()
+    }
+
+    fn save(&self) -> DotViewerResult<()> {
+        let path = (self.path.as_ref())
+            .ok_or(DotViewerError::ViewerError("marks are not persisted".to_string()))?;
+
+        let marks = self.list.items.iter().map(|(name, id)| (name.to_string(), id)).collect();
+        let sidecar = Sidecar { dot: self.dot.clone(), marks };
+        let contents =
+            toml::to_string(&sidecar).map_err(|e| DotViewerError::ViewerError(e.to_string()))?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, contents)?;
+
+        Ok(())
+    }
+}
+
+/// Directory of the sidecar files.
+fn sidecar_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+
+    Some(base.join("dot-viewer").join("marks"))
+}
+
+/// Name of the sidecar file of the dot file in the absolute path `dot`,
+/// by its FNV-1a hash, which is stable across builds unlike the hasher of std.
+fn sidecar_name(dot: &str) -> String {
+    let hash = (dot.bytes()).fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{hash:016x}.toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_fnv1a() {
+        assert_eq!(sidecar_name(""), "cbf29ce484222325.toml");
+        assert_eq!(sidecar_name("a"), "af63dc4c8601ec8c.toml");
+    }
+
+    #[test]
+    fn name_sidecars_apart() {
+        assert_ne!(sidecar_name("/a%b/c.dot"), sidecar_name("/a/b%c.dot"));
+
+        let deep = "/deep".repeat(100);
+        assert_eq!(sidecar_name(&deep).len(), sidecar_name("/a.dot").len());
+    }
+}
@@ -0,0 +1,70 @@
+use crate::viewer::utils::List;
+
+use std::time::{Duration, Instant};
+
+/// Maximum number of messages kept in the log
+const CAPACITY: usize = 256;
+
+/// `Message` is a result of an action or a command, either a success or an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Message {
+    pub text: String,
+    pub error: bool,
+    /// Number of times the message was repeated in a row
+    pub count: usize,
+    /// Instant the message was last logged at
+    pub at: Instant,
+}
+
+/// `Messages` logs past results and errors, the latest first, shown in messages popup.
+pub(crate) struct Messages {
+    pub list: List<Message>,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self { list: List::from_iter(Vec::new()) }
+    }
+}
+
+impl Message {
+    /// Time passed since the message was logged, e.g., `12s` or `3m`.
+    pub fn age(&self) -> String {
+        format_age(self.at.elapsed())
+    }
+}
+
+impl Messages {
+    /// Log `text`, counting it up if it repeats the latest message.
+    pub fn push(&mut self, text: String, error: bool) {
+        let at = Instant::now();
+
+        match self.list.items.first_mut() {
+            Some(latest) if latest.text == text && latest.error == error => {
+                latest.count += 1;
+                latest.at = at;
+            }
+            _ => {
+                self.list.items.insert(0, Message { text, error, count: 1, at });
+                self.list.items.truncate(CAPACITY);
+            }
+        }
+
+        self.list.first();
+    }
+
+    /// Number of errors in the log, counting repeats.
+    pub fn errors(&self) -> usize {
+        self.list.items.iter().filter(|message| message.error).map(|message| message.count).sum()
+    }
+}
+
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m", secs / 60),
+        _ => format!("{}h", secs / 3600),
+    }
+}
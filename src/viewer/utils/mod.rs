@@ -1,13 +1,36 @@
+mod clipboard;
+mod colors;
+mod fields;
+pub(crate) mod htmlparser;
 mod input;
+mod job;
+mod jumps;
+mod lineage;
 mod list;
+mod marks;
+mod messages;
+mod scroll;
+mod sketch;
 mod table;
 mod tabs;
+mod template;
 mod tree;
 mod trie;
 
+pub(crate) use clipboard::Clipboard;
+pub(crate) use colors::{truecolor, Paint};
+pub(crate) use fields::{extract_field, parse_number, Fields, Op};
 pub(crate) use input::Input;
-pub(crate) use list::List;
+pub(crate) use job::{Job, Progress, Task};
+pub(crate) use jumps::Jumps;
+pub(crate) use lineage::{Derivation, Lineage, Origin};
+pub(crate) use list::{contains, List};
+pub(crate) use marks::Marks;
+pub(crate) use messages::Messages;
+pub(crate) use scroll::Scroll;
+pub(crate) use sketch::Sketch;
 pub(crate) use table::Table;
 pub(crate) use tabs::Tabs;
+pub(crate) use template::{label_lines, Template};
 pub(crate) use tree::Tree;
 pub(crate) use trie::Trie;
@@ -0,0 +1,41 @@
+use crate::viewer::utils::list::contains;
+
+use tui::layout::Rect;
+
+/// `Scroll` holds the scroll position of a text without selection, like the attrs panel.
+#[derive(Default)]
+pub(crate) struct Scroll {
+    /// Index of the first visible line
+    pub offset: usize,
+    /// Number of lines in the last rendered frame
+    pub len: usize,
+    /// Number of lines visible in the last rendered frame
+    pub height: usize,
+    /// Area where the text was rendered in the last frame
+    pub area: Rect,
+}
+
+impl Scroll {
+    /// Scroll by `offset` lines, stopping at the first and the last page.
+    pub fn scroll(&mut self, offset: isize) {
+        self.nth(self.offset.saturating_add_signed(offset));
+    }
+
+    /// Scroll such that the `idx`-th line comes first, stopping at the last page.
+    pub fn nth(&mut self, idx: usize) {
+        self.offset = idx.min(self.len.saturating_sub(self.height));
+    }
+
+    /// Record the `area` where `len` lines are rendered, keeping the offset in range.
+    pub fn frame(&mut self, area: Rect, len: usize) {
+        self.area = area;
+        self.height = area.height as usize;
+        self.len = len;
+        self.nth(self.offset);
+    }
+
+    /// Whether the text was rendered over column `x` and row `y`.
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        contains(self.area, x, y)
+    }
+}
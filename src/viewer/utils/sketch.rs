@@ -0,0 +1,607 @@
+use crate::viewer::{
+    error::{DotViewerError, DotViewerResult},
+    utils::list::contains,
+};
+
+use std::collections::{HashMap, HashSet};
+
+use graphviz_rs::prelude::*;
+
+use tui::layout::Rect;
+
+/// Maximum number of nodes to sketch
+const MAX_NODES: usize = 256;
+/// Maximum width of a node label in the sketch
+const MAX_LABEL: usize = 24;
+/// Height of a node box
+const BOX_HEIGHT: usize = 3;
+/// Height of a layer, including the gap to the next layer where edges are routed
+const LAYER_HEIGHT: usize = 6;
+/// Horizontal gap between boxes in a layer
+const GAP: usize = 2;
+/// Number of barycenter sweeps ordering the layers
+const SWEEPS: usize = 4;
+
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+
+/// Box-drawing characters indexed by the directions of lines, `UP | DOWN | LEFT | RIGHT`.
+const LINES: [char; 16] =
+    [' ', '│', '│', '│', '─', '┘', '┐', '┤', '─', '└', '┌', '├', '─', '┴', '┬', '┼'];
+
+/// A node box placed in the sketch.
+pub(crate) struct Placement {
+    pub id: String,
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+}
+
+impl Placement {
+    /// Whether the box covers the cell at column `x` and row `y`.
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + BOX_HEIGHT).contains(&y)
+    }
+}
+
+/// `Sketch` holds a layered (Sugiyama-style) drawing of a neighborhood of a node,
+/// in box-drawing characters.
+pub(crate) struct Sketch {
+    /// Center node of the neighborhood
+    pub center: String,
+    /// Depth of the neighborhood
+    pub depth: usize,
+    /// Lines of the drawing
+    pub lines: Vec<String>,
+    /// Node boxes in each layer, sorted from left to right
+    pub layers: Vec<Vec<Placement>>,
+    /// Selected node box, as layer and index in the layer
+    pub selected: (usize, usize),
+    /// Scroll offset, as row and column
+    pub offset: (usize, usize),
+    /// Area where the drawing was rendered in the last frame
+    pub area: Rect,
+    /// Number of layers visible in the last rendered frame
+    pub height: usize,
+}
+
+/// A vertex in the layered graph, which is either a node or a dummy vertex on a long edge.
+struct Vertex {
+    id: Option<String>,
+    layer: usize,
+    preds: Vec<usize>,
+    succs: Vec<usize>,
+    /// Successors by reversed edges, which are drawn pointing up
+    ups: Vec<usize>,
+}
+
+impl Sketch {
+    /// Sketch the neighborhood of `center` in `graph` up to `depth`.
+    pub fn new(graph: &Graph, center: &str, depth: usize) -> DotViewerResult<Self> {
+        let center = center.to_string();
+
+        let graph = graph.neighbors(&center, depth)?;
+        let mut node_ids = Vec::from_iter(graph.nodes());
+        if node_ids.len() > MAX_NODES {
+            let len = node_ids.len();
+            let msg = format!("too many nodes ({len}) to sketch, try a smaller depth");
+            return Err(DotViewerError::ViewerError(msg));
+        }
+        node_ids.sort_unstable();
+
+        let index: HashMap<&NodeId, usize> =
+            node_ids.iter().enumerate().map(|(idx, &id)| (id, idx)).collect();
+        let mut edges = Vec::new();
+        for (from, &id) in node_ids.iter().enumerate() {
+            let mut tos = Vec::from_iter(graph.tos(id)?);
+            tos.sort_unstable();
+            edges.extend(tos.into_iter().map(|to| (from, index[to])));
+        }
+
+        let ids = node_ids.into_iter().cloned().collect();
+
+        Ok(Self::layout(center, depth, ids, &edges))
+    }
+
+    /// Lay out nodes `ids` with `edges` between their indices, where `center` is one of the ids.
+    fn layout(center: String, depth: usize, ids: Vec<String>, edges: &[(usize, usize)]) -> Self {
+        let vertices = to_vertices(ids, edges);
+        let layers = to_layers(&vertices);
+        let (xs, widths) = place(&vertices, &layers);
+
+        let height = layers.len() * LAYER_HEIGHT - (LAYER_HEIGHT - BOX_HEIGHT);
+        let width = (xs.iter().zip(&widths)).map(|(x, w)| x + w).max().unwrap_or(0);
+        let mut canvas = Canvas::new(width, height);
+
+        for (idx, vertex) in vertices.iter().enumerate() {
+            let (x, y) = (xs[idx], vertex.layer * LAYER_HEIGHT);
+            match &vertex.id {
+                Some(id) => canvas.draw_box(x, y, widths[idx], &label(id)),
+                None => (y..y + BOX_HEIGHT).for_each(|y| canvas.add(x, y, UP | DOWN)),
+            }
+
+            let from = x + widths[idx] / 2;
+            for &succ in &vertex.succs {
+                let arrows = (false, vertices[succ].id.is_some());
+                canvas.draw_edge(from, y, xs[succ] + widths[succ] / 2, arrows);
+            }
+            for &succ in &vertex.ups {
+                let arrows = (vertex.id.is_some(), false);
+                canvas.draw_edge(from, y, xs[succ] + widths[succ] / 2, arrows);
+            }
+        }
+
+        let mut placements: Vec<Vec<Placement>> = (layers.iter())
+            .map(|layer| {
+                (layer.iter())
+                    .filter_map(|&idx| {
+                        let vertex = &vertices[idx];
+                        vertex.id.as_ref().map(|id| Placement {
+                            id: id.clone(),
+                            x: xs[idx],
+                            y: vertex.layer * LAYER_HEIGHT,
+                            width: widths[idx],
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        placements.iter_mut().for_each(|layer| layer.sort_by_key(|placement| placement.x));
+
+        let selected = (placements.iter().enumerate())
+            .find_map(|(l, layer)| {
+                layer.iter().position(|placement| placement.id == center).map(|i| (l, i))
+            })
+            .expect("center should be in the sketch");
+
+        Self {
+            center,
+            depth,
+            lines: canvas.render(),
+            layers: placements,
+            selected,
+            offset: (0, 0),
+            area: Rect::default(),
+            height: 0,
+        }
+    }
+
+    /// Id of the selected node.
+    pub fn selected_id(&self) -> String {
+        let (l, i) = self.selected;
+        self.layers[l][i].id.clone()
+    }
+
+    /// Placement of the selected node.
+    pub fn selected_placement(&self) -> &Placement {
+        let (l, i) = self.selected;
+        &self.layers[l][i]
+    }
+
+    /// Placement of the center node.
+    pub fn center_placement(&self) -> &Placement {
+        (self.layers.iter().flatten())
+            .find(|placement| placement.id == self.center)
+            .expect("center should be in the sketch")
+    }
+
+    /// Select the node on the left in the same layer.
+    pub fn left(&mut self) {
+        let (l, i) = self.selected;
+        self.selected = (l, i.saturating_sub(1));
+    }
+
+    /// Select the node on the right in the same layer.
+    pub fn right(&mut self) {
+        let (l, i) = self.selected;
+        self.selected = (l, (i + 1).min(self.layers[l].len() - 1));
+    }
+
+    /// Move the selection by `offset` non-empty layers, stopping at the first and the last.
+    pub fn scroll(&mut self, offset: isize) {
+        let rows = self.rows();
+        let (l, _) = self.selected;
+
+        if let Some(i) = rows.iter().position(|&row| row == l) {
+            self.nth(i.saturating_add_signed(offset));
+        }
+    }
+
+    /// Select the nearest node in the `idx`-th non-empty layer, or the last if out of range.
+    pub fn nth(&mut self, idx: usize) {
+        let rows = self.rows();
+        if let Some(&l) = rows.get(idx).or(rows.last()) {
+            self.select_layer(l);
+        }
+    }
+
+    /// Number of non-empty layers.
+    pub fn len(&self) -> usize {
+        self.rows().len()
+    }
+
+    /// Record the `area` where the drawing is rendered,
+    /// scrolling such that the selected node is visible in the area.
+    pub fn frame(&mut self, area: Rect) {
+        let (height, width) = (area.height as usize, area.width as usize);
+
+        let placement = self.selected_placement();
+        let (top, bottom) = (placement.y, placement.y + BOX_HEIGHT);
+        let (left, right) = (placement.x, placement.x + placement.width);
+
+        let (mut row, mut col) = self.offset;
+        if top < row {
+            row = top;
+        } else if bottom > row + height {
+            row = bottom.saturating_sub(height);
+        }
+        if left < col {
+            col = left;
+        } else if right > col + width {
+            col = right.saturating_sub(width);
+        }
+
+        self.offset = (row, col);
+        self.area = area;
+        self.height = height / LAYER_HEIGHT;
+    }
+
+    /// Layer and index in the layer of the node box rendered at column `x` and row `y`.
+    pub fn hit(&self, x: u16, y: u16) -> Option<(usize, usize)> {
+        if !contains(self.area, x, y) {
+            return None;
+        }
+
+        let (row, col) = self.offset;
+        let x = (x - self.area.x) as usize + col;
+        let y = (y - self.area.y) as usize + row;
+
+        self.layers.iter().enumerate().find_map(|(l, layer)| {
+            layer.iter().position(|placement| placement.contains(x, y)).map(|i| (l, i))
+        })
+    }
+
+    fn select_layer(&mut self, l: usize) {
+        let placement = self.selected_placement();
+        let center = placement.x + placement.width / 2;
+
+        let i = (self.layers[l].iter().enumerate())
+            .min_by_key(|(_, placement)| (placement.x + placement.width / 2).abs_diff(center))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+
+        self.selected = (l, i);
+    }
+
+    fn rows(&self) -> Vec<usize> {
+        (0..self.layers.len()).filter(|&l| !self.layers[l].is_empty()).collect()
+    }
+}
+
+/// Break cycles by reversing the back edges found by a depth-first search, dropping self-loops.
+/// Returns the nodes in a topological order of the acyclic graph, and its edges marked if reversed.
+fn acyclic(len: usize, edges: &[(usize, usize)]) -> (Vec<usize>, Vec<(usize, usize, bool)>) {
+    let mut succs = vec![Vec::new(); len];
+    for &(from, to) in edges {
+        if from != to {
+            succs[from].push(to);
+        }
+    }
+
+    let mut visited = vec![false; len];
+    let mut active = vec![false; len];
+    let mut backs = HashSet::new();
+    let mut order = Vec::with_capacity(len);
+
+    for root in 0..len {
+        if visited[root] {
+            continue;
+        }
+
+        visited[root] = true;
+        active[root] = true;
+        let mut stack = vec![(root, 0)];
+
+        while let Some(&(node, next)) = stack.last() {
+            match succs[node].get(next) {
+                Some(&succ) => {
+                    let top = stack.len() - 1;
+                    stack[top].1 += 1;
+
+                    if active[succ] {
+                        backs.insert((node, succ));
+                    } else if !visited[succ] {
+                        visited[succ] = true;
+                        active[succ] = true;
+                        stack.push((succ, 0));
+                    }
+                }
+                None => {
+                    active[node] = false;
+                    order.push(node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    // reversed postorder is a topological order, once back edges are reversed
+    order.reverse();
+
+    let edges = (succs.iter().enumerate())
+        .flat_map(|(from, tos)| tos.iter().map(move |&to| (from, to)))
+        .map(|(from, to)| match backs.contains(&(from, to)) {
+            true => (to, from, true),
+            false => (from, to, false),
+        })
+        .collect();
+
+    (order, edges)
+}
+
+/// Assign layers to nodes `ids` by their longest path from the sources, once cycles are broken,
+/// splitting edges spanning multiple layers with dummy vertices.
+fn to_vertices(ids: Vec<String>, edges: &[(usize, usize)]) -> Vec<Vertex> {
+    let (order, edges) = acyclic(ids.len(), edges);
+
+    let mut vertices: Vec<Vertex> = (ids.into_iter())
+        .map(|id| Vertex {
+            id: Some(id),
+            layer: 0,
+            preds: Vec::new(),
+            succs: Vec::new(),
+            ups: Vec::new(),
+        })
+        .collect();
+
+    let mut froms = vec![Vec::new(); vertices.len()];
+    for &(from, to, _) in &edges {
+        froms[to].push(from);
+    }
+    for idx in order {
+        let layer = froms[idx].iter().map(|&from| vertices[from].layer + 1).max().unwrap_or(0);
+        vertices[idx].layer = layer;
+    }
+
+    for (from, to, reversed) in edges {
+        let link = |vertices: &mut Vec<Vertex>, prev: usize, next: usize| {
+            match reversed {
+                true => vertices[prev].ups.push(next),
+                false => vertices[prev].succs.push(next),
+            }
+            vertices[next].preds.push(prev);
+        };
+
+        let mut prev = from;
+        for layer in vertices[from].layer + 1..vertices[to].layer {
+            let dummy = vertices.len();
+            let vertex =
+                Vertex { id: None, layer, preds: Vec::new(), succs: Vec::new(), ups: Vec::new() };
+            vertices.push(vertex);
+            link(&mut vertices, prev, dummy);
+            prev = dummy;
+        }
+
+        link(&mut vertices, prev, to);
+    }
+
+    vertices
+}
+
+/// Order vertices in each layer with barycenter heuristic, reducing edge crossings.
+fn to_layers(vertices: &[Vertex]) -> Vec<Vec<usize>> {
+    let len = vertices.iter().map(|vertex| vertex.layer + 1).max().unwrap_or(0);
+
+    let mut layers = vec![Vec::new(); len];
+    for (idx, vertex) in vertices.iter().enumerate() {
+        layers[vertex.layer].push(idx);
+    }
+
+    let mut positions = vec![0_f64; vertices.len()];
+    let update = |layer: &[usize], positions: &mut Vec<f64>| {
+        for (pos, &idx) in layer.iter().enumerate() {
+            positions[idx] = pos as f64;
+        }
+    };
+    layers.iter().for_each(|layer| update(layer, &mut positions));
+
+    let barycenter = |neighbors: &[usize], positions: &[f64], default: f64| {
+        if neighbors.is_empty() {
+            default
+        } else {
+            neighbors.iter().map(|&idx| positions[idx]).sum::<f64>() / neighbors.len() as f64
+        }
+    };
+
+    for _ in 0..SWEEPS {
+        for layer in layers.iter_mut().skip(1) {
+            let keys: HashMap<usize, f64> = (layer.iter())
+                .map(|&idx| (idx, barycenter(&vertices[idx].preds, &positions, positions[idx])))
+                .collect();
+            layer.sort_by(|a, b| keys[a].total_cmp(&keys[b]));
+            update(layer, &mut positions);
+        }
+
+        for layer in layers.iter_mut().rev().skip(1) {
+            let keys: HashMap<usize, f64> = (layer.iter())
+                .map(|&idx| {
+                    let vertex = &vertices[idx];
+                    let succs = Vec::from_iter(vertex.succs.iter().chain(&vertex.ups).copied());
+                    (idx, barycenter(&succs, &positions, positions[idx]))
+                })
+                .collect();
+            layer.sort_by(|a, b| keys[a].total_cmp(&keys[b]));
+            update(layer, &mut positions);
+        }
+    }
+
+    layers
+}
+
+/// Place vertices in each layer from left to right, centering each layer.
+fn place(vertices: &[Vertex], layers: &[Vec<usize>]) -> (Vec<usize>, Vec<usize>) {
+    let widths: Vec<usize> = (vertices.iter())
+        .map(|vertex| vertex.id.as_ref().map_or(1, |id| label(id).chars().count() + 4))
+        .collect();
+
+    let layer_width = |layer: &Vec<usize>| {
+        layer.iter().map(|&idx| widths[idx]).sum::<usize>() + GAP * layer.len().saturating_sub(1)
+    };
+    let width = layers.iter().map(layer_width).max().unwrap_or(0);
+
+    let mut xs = vec![0; vertices.len()];
+    for layer in layers {
+        let mut x = (width - layer_width(layer)) / 2;
+        for &idx in layer {
+            xs[idx] = x;
+            x += widths[idx] + GAP;
+        }
+    }
+
+    (xs, widths)
+}
+
+fn label(id: &str) -> String {
+    if id.chars().count() <= MAX_LABEL {
+        id.to_string()
+    } else {
+        let id: String = id.chars().take(MAX_LABEL - 1).collect();
+        format!("{id}…")
+    }
+}
+
+/// `Canvas` draws lines by directions of each cell, merging crossing lines into junctions.
+struct Canvas {
+    lines: Vec<Vec<u8>>,
+    texts: HashMap<(usize, usize), char>,
+    arrows: HashMap<(usize, usize), char>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Self { lines: vec![vec![0; width]; height], texts: HashMap::new(), arrows: HashMap::new() }
+    }
+
+    fn add(&mut self, x: usize, y: usize, directions: u8) {
+        self.lines[y][x] |= directions;
+    }
+
+    fn draw_box(&mut self, x: usize, y: usize, width: usize, label: &str) {
+        let right = x + width - 1;
+
+        self.add(x, y, RIGHT | DOWN);
+        self.add(right, y, LEFT | DOWN);
+        self.add(x, y + 1, UP | DOWN);
+        self.add(right, y + 1, UP | DOWN);
+        self.add(x, y + 2, UP | RIGHT);
+        self.add(right, y + 2, UP | LEFT);
+        for x in x + 1..right {
+            self.add(x, y, LEFT | RIGHT);
+            self.add(x, y + 2, LEFT | RIGHT);
+        }
+
+        for (i, c) in label.chars().enumerate() {
+            self.texts.insert((x + 2 + i, y + 1), c);
+        }
+    }
+
+    /// Draw an edge from the bottom of a vertex at column `from` in the layer starting at row `y`,
+    /// to the top of a vertex at column `to` in the next layer,
+    /// starting with an arrow pointing up and ending with an arrow pointing down, as given,
+    /// or joining the vertices otherwise.
+    fn draw_edge(&mut self, from: usize, y: usize, to: usize, (up, down): (bool, bool)) {
+        let (bottom, middle, top) = (y + BOX_HEIGHT - 1, y + BOX_HEIGHT + 1, y + LAYER_HEIGHT);
+
+        self.add(from, bottom + 1, UP | DOWN);
+        if up {
+            self.arrows.insert((from, bottom + 1), '▲');
+        } else {
+            self.add(from, bottom, DOWN);
+        }
+
+        if from == to {
+            self.add(from, middle, UP | DOWN);
+        } else {
+            let (toward, backward) = if from < to { (RIGHT, LEFT) } else { (LEFT, RIGHT) };
+            self.add(from, middle, UP | toward);
+            self.add(to, middle, DOWN | backward);
+            for x in from.min(to) + 1..from.max(to) {
+                self.add(x, middle, LEFT | RIGHT);
+            }
+        }
+
+        self.add(to, top - 1, UP | DOWN);
+        if down {
+            self.arrows.insert((to, top - 1), '▼');
+        } else {
+            self.add(to, top, UP);
+        }
+    }
+
+    fn render(&self) -> Vec<String> {
+        (self.lines.iter().enumerate())
+            .map(|(y, line)| {
+                (line.iter().enumerate())
+                    .map(|(x, &directions)| {
+                        match (self.texts.get(&(x, y)), self.arrows.get(&(x, y))) {
+                            (Some(&c), _) => c,
+                            (None, Some(&arrow)) if directions == UP | DOWN => arrow,
+                            _ => LINES[directions as usize],
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sketch(ids: &[&str], edges: &[(usize, usize)]) -> Sketch {
+        let ids = ids.iter().map(|id| id.to_string()).collect();
+        Sketch::layout("a".to_string(), 1, ids, edges)
+    }
+
+    fn layers(sketch: &Sketch) -> Vec<Vec<&str>> {
+        (sketch.layers.iter())
+            .map(|layer| layer.iter().map(|placement| placement.id.as_str()).collect())
+            .collect()
+    }
+
+    fn count(sketch: &Sketch, arrow: char) -> usize {
+        sketch.lines.iter().map(|line| line.matches(arrow).count()).sum()
+    }
+
+    #[test]
+    fn layer_by_longest_paths() {
+        let sketch = sketch(&["a", "b", "c"], &[(0, 1), (1, 2), (0, 2)]);
+
+        // edges into c join into one arrow
+        assert_eq!(layers(&sketch), vec![vec!["a"], vec!["b"], vec!["c"]]);
+        assert_eq!(count(&sketch, '▼'), 2);
+        assert_eq!(count(&sketch, '▲'), 0);
+    }
+
+    #[test]
+    fn reverse_back_edges() {
+        let (order, edges) = acyclic(2, &[(0, 1), (1, 0)]);
+
+        assert_eq!(order, vec![0, 1]);
+        assert_eq!(edges, vec![(0, 1, false), (0, 1, true)]);
+    }
+
+    #[test]
+    fn sketch_cycles() {
+        // a -> b -> c -> a, with a self-loop on b
+        let sketch = sketch(&["a", "b", "c"], &[(0, 1), (1, 2), (2, 0), (1, 1)]);
+
+        assert_eq!(layers(&sketch), vec![vec!["a"], vec!["b"], vec!["c"]]);
+        assert_eq!(count(&sketch, '▼'), 2);
+        assert_eq!(count(&sketch, '▲'), 1);
+        assert_eq!(sketch.selected_id(), "a");
+    }
+}
@@ -1,14 +1,25 @@
+use crate::viewer::utils::list::{contains, follow};
+
 use rayon::prelude::*;
-use tui::widgets::TableState;
+use tui::{layout::Rect, widgets::TableState};
+
+/// Number of lines taken by a row, including its bottom margin
+const ROW_HEIGHT: u16 = 2;
 
 pub(crate) struct Table {
     pub state: TableState,
     pub header: Vec<String>,
     pub rows: Vec<Vec<String>>,
+    /// Area where the rows were rendered in the last frame
+    pub area: Rect,
+    /// Index of the first row visible in the last rendered frame
+    pub offset: usize,
+    /// Number of rows visible in the last rendered frame
+    pub height: usize,
 }
 
 impl Table {
-    pub fn new(header: &[&str], rows: &[&[&str]]) -> Self {
+    pub fn new(header: &[&str], rows: Vec<Vec<String>>) -> Self {
         let mut state = TableState::default();
 
         if !rows.is_empty() {
@@ -17,10 +28,11 @@ impl Table {
 
         let header: Vec<String> = header.par_iter().map(|s| s.to_string()).collect();
 
-        let rows: Vec<Vec<String>> =
-            rows.par_iter().map(|row| row.iter().map(|s| s.to_string()).collect()).collect();
+        let area = Rect::default();
+        let offset = 0;
+        let height = 0;
 
-        Self { state, header, rows }
+        Self { state, header, rows, area, offset, height }
     }
 
     pub fn next(&mut self) {
@@ -38,4 +50,44 @@ impl Table {
 
         self.state.select(Some(i));
     }
+
+    /// Move the selection by `offset`, wrapping around for a single step,
+    /// but stopping at the first and the last for counts, as Vim does.
+    pub fn step(&mut self, offset: isize) {
+        match offset {
+            1 => self.next(),
+            -1 => self.previous(),
+            _ => self.scroll(offset),
+        }
+    }
+
+    /// Move the selection by `offset`, stopping at the first and the last.
+    pub fn scroll(&mut self, offset: isize) {
+        if let Some(i) = self.state.selected() {
+            let i = i.saturating_add_signed(offset);
+            self.nth(i);
+        }
+    }
+
+    /// Select the `idx`-th row, or the last if out of range.
+    pub fn nth(&mut self, idx: usize) {
+        if !self.rows.is_empty() {
+            self.state.select(Some(idx.min(self.rows.len() - 1)));
+        }
+    }
+
+    /// Record the `area` where the rows are rendered below the header,
+    /// scrolling as `tui` does such that positions in the area can be mapped to rows.
+    pub fn frame(&mut self, area: Rect) {
+        self.area = area;
+        self.height = (area.height / ROW_HEIGHT) as usize;
+        self.offset = follow(self.offset, self.state.selected(), self.height, self.rows.len());
+    }
+
+    /// Index of the row rendered at column `x` and row `y`.
+    pub fn hit(&self, x: u16, y: u16) -> Option<usize> {
+        (contains(self.area, x, y))
+            .then(|| self.offset + ((y - self.area.y) / ROW_HEIGHT) as usize)
+            .filter(|&idx| idx < self.rows.len())
+    }
 }
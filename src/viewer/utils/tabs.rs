@@ -1,19 +1,48 @@
 #![allow(dead_code)]
 
-use crate::viewer::error::{DotViewerError, DotViewerResult};
+use crate::viewer::{
+    error::{DotViewerError, DotViewerResult},
+    utils::list::contains,
+};
+
+use tui::layout::Rect;
+
+/// Maximum number of closed tabs kept to be reopened.
+const CLOSED: usize = 16;
 
 // https://github.com/fdehau/tui-rs/blob/master/examples/tabs.rs
 pub(crate) struct Tabs<T> {
     pub state: usize,
     pub tabs: Vec<T>,
+    /// Closed tabs with their indices, the latest one last
+    pub closed: Vec<(usize, T)>,
+    /// Other pane of the split screen, if split
+    pub split: Option<Split>,
+    /// Area where the titles were rendered in the last frame
+    pub area: Rect,
+}
+
+/// `Split` is the other pane of a split screen, showing another tab beside the selected one.
+pub(crate) struct Split {
+    /// Index of the tab shown in the other pane
+    pub other: usize,
+    /// Whether the selected tab is shown in the left pane
+    pub left: bool,
+    /// Whether selecting a node selects the same node in the other pane
+    pub sync: bool,
+    /// Area where the other pane was rendered in the last frame
+    pub area: Rect,
 }
 
 impl<T> std::iter::FromIterator<T> for Tabs<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let state = 0;
         let tabs = Vec::from_iter(iter);
+        let closed = Vec::new();
+        let split = None;
+        let area = Rect::default();
 
-        Self { state, tabs }
+        Self { state, tabs, closed, split, area }
     }
 }
 
@@ -38,18 +67,141 @@ impl<T> Tabs<T> {
     }
 
     pub fn close(&mut self) -> DotViewerResult<()> {
-        if self.state == 0 {
-            return Err(DotViewerError::ViewerError("cannot close the first tab".to_string()));
+        self.close_at(self.state)
+    }
+
+    /// Close the tab at `idx`, keeping the selected tab selected if it is another one.
+    pub fn close_at(&mut self, idx: usize) -> DotViewerResult<()> {
+        if self.tabs.len() == 1 {
+            return Err(DotViewerError::ViewerError("cannot close the last tab".to_string()));
         }
 
-        self.tabs.remove(self.state);
-        if self.state == self.tabs.len() {
+        let tab = self.tabs.remove(idx);
+        self.remember(idx, tab);
+
+        // the screen is restored when the tab of either pane is closed, selecting the remaining one
+        let shift = |other: usize| if other > idx { other - 1 } else { other };
+        match self.split.take() {
+            Some(split) if split.other == idx => {}
+            Some(split) if self.state == idx => {
+                self.state = shift(split.other);
+                return Ok(());
+            }
+            Some(mut split) => {
+                split.other = shift(split.other);
+                self.split = Some(split);
+            }
+            None => {}
+        }
+
+        if self.state > idx || self.state == self.tabs.len() {
             self.state -= 1;
         }
 
         Ok(())
     }
 
+    /// Close all tabs but the selected one.
+    pub fn close_others(&mut self) -> DotViewerResult<()> {
+        if self.tabs.len() == 1 {
+            return Err(DotViewerError::ViewerError("no other tab to close".to_string()));
+        }
+
+        // closed from the last, such that reopening restores them from the first
+        let state = self.state;
+        for idx in (0..self.tabs.len()).rev().filter(|&idx| idx != state) {
+            self.close_at(idx)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reopen the latest closed tab at where it was.
+    pub fn reopen(&mut self) -> DotViewerResult<()> {
+        let (idx, tab) = (self.closed.pop())
+            .ok_or(DotViewerError::ViewerError("no closed tab to reopen".to_string()))?;
+
+        let idx = idx.min(self.tabs.len());
+        self.tabs.insert(idx, tab);
+        self.state = idx;
+
+        if let Some(split) = self.split.as_mut().filter(|split| split.other >= idx) {
+            split.other += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Move the selected tab to `idx`.
+    pub fn move_to(&mut self, idx: usize) -> DotViewerResult<()> {
+        if idx >= self.tabs.len() {
+            return Err(DotViewerError::ViewerError(format!("no tab at {}", idx + 1)));
+        }
+
+        let tab = self.tabs.remove(self.state);
+        self.tabs.insert(idx, tab);
+
+        if let Some(split) = &mut self.split {
+            if split.other == self.state {
+                split.other = idx;
+            } else {
+                if split.other > self.state {
+                    split.other -= 1;
+                }
+                if split.other >= idx {
+                    split.other += 1;
+                }
+            }
+        }
+        self.state = idx;
+
+        Ok(())
+    }
+
+    /// Split the screen, showing the tab at `other` in the other pane.
+    pub fn split(&mut self, other: usize) -> DotViewerResult<()> {
+        if other >= self.tabs.len() {
+            return Err(DotViewerError::ViewerError(format!("no tab at {}", other + 1)));
+        }
+        if other == self.state {
+            let msg = format!("tab {} is already shown, pick another tab", other + 1);
+            return Err(DotViewerError::ViewerError(msg));
+        }
+
+        match &mut self.split {
+            Some(split) => split.other = other,
+            None => {
+                let split = Split { other, left: true, sync: false, area: Rect::default() };
+                self.split = Some(split);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Switch the selected tab with the one in the other pane of the split screen.
+    pub fn switch(&mut self) -> DotViewerResult<()> {
+        let split = (self.split.as_mut())
+            .ok_or(DotViewerError::ViewerError("the screen is not split".to_string()))?;
+
+        std::mem::swap(&mut self.state, &mut split.other);
+        split.left = !split.left;
+
+        Ok(())
+    }
+
+    /// Whether column `x` and row `y` is in the other pane of the split screen.
+    pub fn hit_other(&self, x: u16, y: u16) -> bool {
+        matches!(&self.split, Some(split) if contains(split.area, x, y))
+    }
+
+    fn remember(&mut self, idx: usize, tab: T) {
+        self.closed.push((idx, tab));
+        if self.closed.len() > CLOSED {
+            self.closed.remove(0);
+        }
+    }
+
     pub fn select(&mut self, state: usize) {
         if state < self.tabs.len() {
             self.state = state;
@@ -59,4 +211,165 @@ impl<T> Tabs<T> {
     pub fn selected(&mut self) -> &mut T {
         &mut self.tabs[self.state]
     }
+
+    /// Index of the tab whose title, given its `widths`, is rendered at column `x` and row `y`.
+    /// Titles are rendered as `tui` tabs, padded by a space on both sides and divided by `|`.
+    pub fn hit(&self, widths: impl IntoIterator<Item = usize>, x: u16, y: u16) -> Option<usize> {
+        if !contains(self.area, x, y) {
+            return None;
+        }
+
+        let x = (x - self.area.x) as usize;
+        let mut left = 0;
+        for (idx, width) in widths.into_iter().enumerate() {
+            let right = left + width + 2;
+            if x < right {
+                return Some(idx);
+            }
+            left = right + 1;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tabs(len: usize, state: usize) -> Tabs<usize> {
+        let mut tabs = Tabs::from_iter(0..len);
+        tabs.select(state);
+        tabs
+    }
+
+    #[test]
+    fn close_tabs() {
+        let mut tabs = tabs(4, 2);
+
+        tabs.close_at(0).unwrap();
+        assert_eq!((tabs.tabs.as_slice(), tabs.state), ([1, 2, 3].as_slice(), 1));
+
+        tabs.close_at(2).unwrap();
+        assert_eq!((tabs.tabs.as_slice(), tabs.state), ([1, 2].as_slice(), 1));
+
+        tabs.close().unwrap();
+        assert_eq!((tabs.tabs.as_slice(), tabs.state), ([1].as_slice(), 0));
+
+        assert!(tabs.close().is_err());
+    }
+
+    #[test]
+    fn close_other_tabs() {
+        let mut tabs = tabs(4, 2);
+
+        tabs.close_others().unwrap();
+        assert_eq!((tabs.tabs.as_slice(), tabs.state), ([2].as_slice(), 0));
+        assert!(tabs.close_others().is_err());
+
+        tabs.reopen().unwrap();
+        tabs.reopen().unwrap();
+        tabs.reopen().unwrap();
+        assert_eq!((tabs.tabs.as_slice(), tabs.state), ([0, 1, 2, 3].as_slice(), 3));
+    }
+
+    #[test]
+    fn reopen_tabs() {
+        let mut tabs = tabs(4, 0);
+
+        tabs.close_at(1).unwrap();
+        tabs.close_at(2).unwrap();
+        assert_eq!(tabs.tabs, [0, 2]);
+
+        tabs.reopen().unwrap();
+        assert_eq!((tabs.tabs.as_slice(), tabs.state), ([0, 2, 3].as_slice(), 2));
+        tabs.reopen().unwrap();
+        assert_eq!((tabs.tabs.as_slice(), tabs.state), ([0, 1, 2, 3].as_slice(), 1));
+        assert!(tabs.reopen().is_err());
+    }
+
+    #[test]
+    fn forget_old_closed_tabs() {
+        let mut tabs = tabs(CLOSED + 2, 0);
+
+        tabs.close_others().unwrap();
+        assert_eq!(tabs.closed.len(), CLOSED);
+        assert_eq!(tabs.closed.last().map(|(_, tab)| *tab), Some(1));
+    }
+
+    #[test]
+    fn move_tabs() {
+        let mut tabs = tabs(4, 0);
+
+        tabs.move_to(2).unwrap();
+        assert_eq!((tabs.tabs.as_slice(), tabs.state), ([1, 2, 0, 3].as_slice(), 2));
+
+        tabs.move_to(0).unwrap();
+        assert_eq!((tabs.tabs.as_slice(), tabs.state), ([0, 1, 2, 3].as_slice(), 0));
+
+        assert!(tabs.move_to(4).is_err());
+    }
+
+    fn split(len: usize, state: usize, other: usize) -> Tabs<usize> {
+        let mut tabs = tabs(len, state);
+        tabs.split(other).unwrap();
+        tabs
+    }
+
+    fn other(tabs: &Tabs<usize>) -> Option<usize> {
+        tabs.split.as_ref().map(|split| tabs.tabs[split.other])
+    }
+
+    #[test]
+    fn split_tabs() {
+        let mut tabs = tabs(3, 0);
+
+        assert!(tabs.split(0).is_err());
+        assert!(tabs.split(3).is_err());
+
+        tabs.split(2).unwrap();
+        assert_eq!(other(&tabs), Some(2));
+
+        tabs.switch().unwrap();
+        assert_eq!((tabs.state, other(&tabs)), (2, Some(0)));
+        assert!(!tabs.split.as_ref().unwrap().left);
+    }
+
+    #[test]
+    fn shift_split_on_close() {
+        let mut tabs = split(4, 1, 3);
+        tabs.close_at(0).unwrap();
+        assert_eq!((tabs.tabs[tabs.state], other(&tabs)), (1, Some(3)));
+
+        // closing either pane restores the screen
+        let mut tabs = split(4, 1, 3);
+        tabs.close_at(3).unwrap();
+        assert_eq!((tabs.tabs[tabs.state], other(&tabs)), (1, None));
+
+        let mut tabs = split(4, 1, 3);
+        tabs.close().unwrap();
+        assert_eq!((tabs.tabs[tabs.state], other(&tabs)), (3, None));
+    }
+
+    #[test]
+    fn shift_split_on_reopen() {
+        let mut tabs = split(4, 3, 2);
+        tabs.close_at(0).unwrap();
+        tabs.reopen().unwrap();
+        assert_eq!((tabs.tabs[tabs.state], other(&tabs)), (0, Some(2)));
+    }
+
+    #[test]
+    fn shift_split_on_move() {
+        let mut tabs = split(4, 0, 2);
+        tabs.move_to(3).unwrap();
+        assert_eq!((tabs.tabs.as_slice(), other(&tabs)), ([1, 2, 3, 0].as_slice(), Some(2)));
+
+        tabs.move_to(1).unwrap();
+        assert_eq!((tabs.tabs.as_slice(), other(&tabs)), ([1, 0, 2, 3].as_slice(), Some(2)));
+
+        tabs.switch().unwrap();
+        tabs.move_to(0).unwrap();
+        assert_eq!((tabs.tabs.as_slice(), other(&tabs)), ([2, 1, 0, 3].as_slice(), Some(0)));
+    }
 }
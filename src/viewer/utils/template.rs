@@ -0,0 +1,245 @@
+use crate::viewer::{
+    error::{DotViewerError, DotViewerResult},
+    utils::htmlparser,
+};
+
+use std::str::FromStr;
+
+use graphviz_rs::prelude::*;
+
+/// `Template` renders a node into a list item from its attributes, e.g., `{label:first_line} [{shape}]`.
+///
+/// A placeholder `{name}` is replaced with the value of the attribute `name`, or the node id for `id`,
+/// where labels are decoded from HTML and escapes into lines joined by spaces.
+/// A placeholder may apply a filter as `{name:filter}`, which is either
+/// `first_line` keeping the first line of the value, or a number keeping at most that many characters.
+/// Braces are escaped as `{{` and `}}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Attr { name: String, filter: Option<Filter> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Filter {
+    FirstLine,
+    Width(usize),
+}
+
+impl FromStr for Template {
+    type Err = DotViewerError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => return Err(error(template, "unclosed {")),
+                        }
+                    }
+
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(parse_attr(template, &placeholder)?);
+                }
+                '}' => return Err(error(template, "unmatched }")),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+
+        Ok(Self { parts })
+    }
+}
+
+impl Default for Template {
+    fn default() -> Self {
+        "{label:first_line}".parse().expect("default template should be valid")
+    }
+}
+
+impl Template {
+    /// Render `node` into a line, or its id if the line is blank.
+    pub fn render(&self, node: &Node) -> String {
+        self.render_with(node.id(), |name| match name {
+            "id" => vec![node.id().clone()],
+            "label" => label_lines(node),
+            name => node.attrs().get(name).map(|attr| attr.value().clone()).into_iter().collect(),
+        })
+    }
+
+    /// Render the node `id` into a line, or `id` if the line is blank,
+    /// where `lines` gives the lines of the value of an attribute.
+    fn render_with(&self, id: &str, lines: impl Fn(&str) -> Vec<String>) -> String {
+        let mut line = String::new();
+
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => line.push_str(text),
+                Part::Attr { name, filter } => {
+                    let lines = lines(name);
+
+                    let value = match filter {
+                        Some(Filter::FirstLine) => lines.into_iter().next().unwrap_or_default(),
+                        Some(Filter::Width(width)) => {
+                            let value = lines.join(" ");
+                            if value.chars().count() > *width {
+                                let value: String =
+                                    value.chars().take(width.saturating_sub(1)).collect();
+                                format!("{value}…")
+                            } else {
+                                value
+                            }
+                        }
+                        None => lines.join(" "),
+                    };
+                    line.push_str(&value);
+                }
+            }
+        }
+
+        if line.trim().is_empty() {
+            id.to_string()
+        } else {
+            line
+        }
+    }
+}
+
+/// Lines of the label of `node`, decoded from HTML and escapes,
+/// or its id if it has no label.
+pub(crate) fn label_lines(node: &Node) -> Vec<String> {
+    let id = node.id();
+
+    let texts = match node.attrs().get("label") {
+        Some(label) if label.is_html() => htmlparser::parse(label.value()),
+        Some(label) => vec![label.value().replace("\\N", id)],
+        None => vec![id.clone()],
+    };
+
+    let mut lines = Vec::new();
+    for text in texts {
+        let text = text.replace("\\l", "\n").replace("\\r", "\n").replace("\\n", "\n");
+        let text = text.trim();
+        if !text.is_empty() {
+            lines.extend(text.lines().map(|line| line.to_string()));
+        }
+    }
+
+    lines
+}
+
+fn parse_attr(template: &str, placeholder: &str) -> DotViewerResult<Part> {
+    let (name, filter) = match placeholder.split_once(':') {
+        Some((name, filter)) => (name, Some(filter)),
+        None => (placeholder, None),
+    };
+
+    if name.is_empty() {
+        return Err(error(template, "empty placeholder {}"));
+    }
+
+    let filter = match filter {
+        Some("first_line") => Some(Filter::FirstLine),
+        Some(filter) => match filter.parse() {
+            Ok(width) => Some(Filter::Width(width)),
+            Err(_) => return Err(error(template, &format!("unknown filter {filter}"))),
+        },
+        None => None,
+    };
+
+    Ok(Part::Attr { name: name.to_string(), filter })
+}
+
+fn error(template: &str, msg: &str) -> DotViewerError {
+    DotViewerError::ConfigError(format!("template {template:?}: {msg}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str, attrs: &[(&str, &[&str])]) -> String {
+        let template: Template = template.parse().unwrap();
+        template.render_with("n0", |name| {
+            let lines = attrs.iter().find(|(key, _)| *key == name).map(|(_, lines)| *lines);
+            lines.unwrap_or_default().iter().map(|line| line.to_string()).collect()
+        })
+    }
+
+    fn attr(name: &str, filter: Option<Filter>) -> Part {
+        Part::Attr { name: name.to_string(), filter }
+    }
+
+    #[test]
+    fn parse_placeholders() {
+        let template: Template = "{label:first_line} [{shape}] {id:20}".parse().unwrap();
+        let parts = vec![
+            attr("label", Some(Filter::FirstLine)),
+            Part::Text(" [".to_string()),
+            attr("shape", None),
+            Part::Text("] ".to_string()),
+            attr("id", Some(Filter::Width(20))),
+        ];
+
+        assert_eq!(template.parts, parts);
+    }
+
+    #[test]
+    fn parse_escapes() {
+        let template: Template = "{{{id}}}".parse().unwrap();
+        let parts =
+            vec![Part::Text("{".to_string()), attr("id", None), Part::Text("}".to_string())];
+
+        assert_eq!(template.parts, parts);
+    }
+
+    #[test]
+    fn reject_invalid_templates() {
+        for template in ["{label", "label}", "{}", "{:20}", "{label:last_line}", "{label:-1}"] {
+            assert!(template.parse::<Template>().is_err(), "{template}");
+        }
+    }
+
+    #[test]
+    fn render_filters() {
+        let attrs: &[(&str, &[&str])] = &[("label", &["conv1", "3x3"]), ("shape", &["box"])];
+
+        assert_eq!(render("{label} [{shape}]", attrs), "conv1 3x3 [box]");
+        assert_eq!(render("{label:first_line}", attrs), "conv1");
+        assert_eq!(render("{label:5}", attrs), "conv…");
+        assert_eq!(render("{label:9}", attrs), "conv1 3x3");
+        assert_eq!(render("{{{shape}}}", attrs), "{box}");
+    }
+
+    #[test]
+    fn render_blank_as_id() {
+        assert_eq!(render("{color}", &[]), "n0");
+        assert_eq!(render(" {color:first_line} ", &[]), "n0");
+        assert_eq!(render("{color}.", &[]), ".");
+    }
+}
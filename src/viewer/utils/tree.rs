@@ -1,54 +1,113 @@
 #![allow(dead_code)]
 
+use crate::viewer::utils::list::contains;
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
 use graphviz_rs::prelude::*;
 
-use tui_tree_widget::{TreeItem, TreeState};
+use tui::layout::Rect;
+use tui_tree_widget::{flatten, TreeItem, TreeState};
 
 use rayon::prelude::*;
 
+#[derive(Clone)]
 pub(crate) struct Item {
     id: String,
     children: Vec<Item>,
+    stats: Stats,
+}
+
+/// Statistics of a subgraph, including those of its descendants.
+#[derive(Clone, Copy)]
+struct Stats {
+    subgraphs: usize,
+    nodes: usize,
+    edges: usize,
+    /// Number of edges coming from outside the subgraph
+    inbound: usize,
+    /// Number of edges going outside the subgraph
+    outbound: usize,
+}
+
+/// Order of sibling subgraphs in the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TreeSort {
+    /// By ids, in ascending order
+    Name,
+    /// By numbers of nodes, in descending order
+    Size,
+}
+
+impl TreeSort {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Size => "size",
+        }
+    }
 }
 
 // https://github.com/EdJoPaTo/tui-rs-tree-widget/blob/main/examples/util/mod.rs
 pub(crate) struct Tree {
     pub state: TreeState,
     pub tree: Vec<TreeItem<'static>>,
+    /// All subgraphs
     items: Vec<Item>,
+    /// Subgraphs shown in the tree, filtered and sorted
+    shown: Vec<Item>,
+    /// Keyword that shown subgraphs or their descendants contain
+    pub filter: String,
+    pub sort: TreeSort,
+    /// Area where the items were rendered in the last frame
+    pub area: Rect,
+    /// Number of items visible in the last rendered frame
+    pub height: usize,
 }
 
 impl Tree {
-    pub fn from_graph(graph: &Graph) -> Self {
+    /// Constructs a new `Tree` of the subgraphs in `graph`, given the `incident` edges of each node.
+    pub fn from_graph(graph: &Graph, incident: &HashMap<NodeId, Vec<EdgeId>>) -> Self {
         let root = graph.search_subgraph(graph.id()).unwrap().id();
 
-        let item = to_item(root, graph);
-        let tree = to_tree(&item, graph);
+        let (item, _, _) = to_item(root, graph, incident);
+        let tree = to_tree(&item);
 
         let items = vec![item];
+        let shown = items.clone();
         let tree = vec![tree];
 
         let mut state = TreeState::default();
         state.select_first();
         state.toggle_selected();
 
-        Self { state, items, tree }
+        let filter = String::new();
+        let sort = TreeSort::Name;
+
+        let area = Rect::default();
+        let height = 0;
+
+        Self { state, items, shown, tree, filter, sort, area, height }
     }
 
     pub fn selected(&self) -> Option<String> {
-        let mut idxs = self.state.selected();
-
-        if idxs.is_empty() {
-            return None;
-        }
+        item(&self.shown, &self.state.selected()).map(|item| item.id.clone())
+    }
 
-        let idx = idxs.remove(0);
-        let mut item = &self.items[idx];
-        for idx in idxs {
-            item = &item.children[idx];
+    /// Select the subgraph `id`, opening its ancestors.
+    /// Returns whether the subgraph is in the tree.
+    pub fn select_id(&mut self, id: &str) -> bool {
+        match path(&self.shown, id) {
+            Some(path) => {
+                (1..path.len()).for_each(|len| {
+                    self.state.open(path[..len].to_vec());
+                });
+                self.state.select(path);
+                true
+            }
+            None => false,
         }
-
-        Some(item.id.clone())
     }
 
     pub fn first(&mut self) {
@@ -78,35 +137,212 @@ impl Tree {
     pub fn toggle(&mut self) {
         self.state.toggle_selected();
     }
+
+    pub fn expand_all(&mut self) {
+        let mut paths = Vec::new();
+        parents(&self.shown, &mut Vec::new(), &mut paths);
+
+        paths.into_iter().for_each(|path| {
+            self.state.open(path);
+        });
+    }
+
+    pub fn collapse_all(&mut self) {
+        self.state.close_all();
+        self.state.select_first();
+    }
+
+    /// Show only the subgraphs whose ids, or those of their descendants, contain `filter`,
+    /// ignoring cases.
+    pub fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        self.refresh();
+
+        if !self.filter.is_empty() {
+            self.expand_all();
+        }
+    }
+
+    pub fn toggle_sort(&mut self) {
+        self.sort = match self.sort {
+            TreeSort::Name => TreeSort::Size,
+            TreeSort::Size => TreeSort::Name,
+        };
+        self.refresh();
+    }
+
+    /// Rebuild the shown tree, keeping the selected subgraph selected and the opened subgraphs
+    /// opened if still shown.
+    fn refresh(&mut self) {
+        let selected = self.selected();
+        let opened: Vec<String> = (self.state.get_all_opened().iter())
+            .filter_map(|path| item(&self.shown, path).map(|item| item.id.clone()))
+            .collect();
+
+        let filter = self.filter.to_lowercase();
+        self.shown =
+            (self.items.iter()).filter_map(|item| shown(item, &filter, self.sort)).collect();
+        self.tree = self.shown.iter().map(to_tree).collect();
+
+        self.state.close_all();
+        for id in opened {
+            if let Some(path) = path(&self.shown, &id) {
+                self.state.open(path);
+            }
+        }
+        match selected {
+            Some(id) if self.select_id(&id) => {}
+            _ => self.state.select_first(),
+        }
+    }
+
+    /// Move the selection by `offset` among the visible items,
+    /// stopping at the first and the last.
+    pub fn scroll(&mut self, offset: isize) {
+        let visible = flatten(&self.state.get_all_opened(), &self.tree);
+        let selected = self.state.selected();
+
+        if let Some(i) = visible.iter().position(|item| item.identifier == selected) {
+            let i = i.saturating_add_signed(offset);
+            self.nth(i);
+        }
+    }
+
+    /// Select the `idx`-th visible item, or the last if out of range.
+    pub fn nth(&mut self, idx: usize) {
+        let visible = flatten(&self.state.get_all_opened(), &self.tree);
+
+        if let Some(item) = visible.get(idx).or(visible.last()) {
+            let identifier = item.identifier.clone();
+            self.state.select(identifier);
+        }
+    }
+
+    /// Record the `area` where the items are rendered, one item per row.
+    pub fn frame(&mut self, area: Rect) {
+        self.area = area;
+        self.height = area.height as usize;
+    }
+
+    /// Index of the visible item rendered at column `x` and row `y`.
+    pub fn hit(&self, x: u16, y: u16) -> Option<usize> {
+        (contains(self.area, x, y))
+            .then(|| self.state.get_offset() + (y - self.area.y) as usize)
+            .filter(|&idx| idx < self.len())
+    }
+
+    /// Number of visible items.
+    pub fn len(&self) -> usize {
+        flatten(&self.state.get_all_opened(), &self.tree).len()
+    }
 }
 
-fn to_item(root: &String, graph: &Graph) -> Item {
-    let id = root.clone();
-
-    let children = graph.collect_subgraphs(root).expect("root should exist in the graph");
-    let mut children: Vec<Item> = children
-        .par_iter()
-        .map(|&id| {
-            let child = graph.search_subgraph(id).unwrap();
-            to_item(child.id(), graph)
-        })
-        .collect();
-    children.sort_by(|a, b| (a.id).cmp(&b.id));
+/// Item at the indices `path` from the root.
+fn item<'a>(items: &'a [Item], path: &[usize]) -> Option<&'a Item> {
+    let (&idx, path) = path.split_first()?;
+    let mut item = items.get(idx)?;
+    for &idx in path {
+        item = item.children.get(idx)?;
+    }
+
+    Some(item)
+}
+
+/// Indices of the items from the root to the item `id`.
+fn path(items: &[Item], id: &str) -> Option<Vec<usize>> {
+    items.iter().enumerate().find_map(|(idx, item)| {
+        if item.id == id {
+            Some(vec![idx])
+        } else {
+            path(&item.children, id).map(|mut path| {
+                path.insert(0, idx);
+                path
+            })
+        }
+    })
+}
 
-    Item { id, children }
+/// Collect the paths of all items having children, under the path `prefix`.
+fn parents(items: &[Item], prefix: &mut Vec<usize>, paths: &mut Vec<Vec<usize>>) {
+    for (idx, item) in items.iter().enumerate() {
+        if !item.children.is_empty() {
+            prefix.push(idx);
+            paths.push(prefix.clone());
+            parents(&item.children, prefix, paths);
+            prefix.pop();
+        }
+    }
 }
 
-fn to_tree(root: &Item, graph: &Graph) -> TreeItem<'static> {
-    let id = &root.id;
-    let children = &root.children;
+/// The `item` with its descendants filtered by lowercase `filter` and sorted by `sort`,
+/// or `None` if neither it nor its descendants match.
+fn shown(item: &Item, filter: &str, sort: TreeSort) -> Option<Item> {
+    let mut children: Vec<Item> =
+        item.children.iter().filter_map(|child| shown(child, filter, sort)).collect();
+
+    match sort {
+        TreeSort::Name => children.sort_by(|a, b| a.id.cmp(&b.id)),
+        TreeSort::Size => children.sort_by_key(|item| Reverse(item.stats.nodes)),
+    }
+
+    let matches = item.id.to_lowercase().contains(filter);
+    (matches || !children.is_empty()).then(|| Item { children, ..item.clone() })
+}
+
+/// The item of the subgraph `root`, built bottom-up from those of its children,
+/// with the nodes and the edges in the subgraph including its descendants.
+///
+/// Boundary edges are counted over the `incident` edges of each node in the subgraph,
+/// which takes time in the number of edges times the depth of the subgraph tree.
+fn to_item<'a>(
+    root: &'a GraphId,
+    graph: &'a Graph,
+    incident: &HashMap<NodeId, Vec<EdgeId>>,
+) -> (Item, HashSet<&'a NodeId>, HashSet<&'a EdgeId>) {
+    let subgraph = graph.search_subgraph(root).expect("root should exist in the graph");
+
+    let descendants: Vec<_> =
+        (subgraph.subgraphs().into_par_iter()).map(|id| to_item(id, graph, incident)).collect();
+
+    let mut nodes = subgraph.nodes();
+    let mut edges = subgraph.edges();
+    let mut children = Vec::with_capacity(descendants.len());
+    for (child, child_nodes, child_edges) in descendants {
+        nodes.extend(child_nodes);
+        edges.extend(child_edges);
+        children.push(child);
+    }
+    children.sort_by(|a, b| (a.id).cmp(&b.id));
+
+    let (mut inbound, mut outbound) = (0, 0);
+    for &id in &nodes {
+        for edge in incident.get(id).into_iter().flatten() {
+            if edge.to() == id && !nodes.contains(edge.from()) {
+                inbound += 1;
+            }
+            if edge.from() == id && !nodes.contains(edge.to()) {
+                outbound += 1;
+            }
+        }
+    }
+
+    let stats = Stats {
+        subgraphs: children.len(),
+        nodes: nodes.len(),
+        edges: edges.len(),
+        inbound,
+        outbound,
+    };
+
+    (Item { id: root.clone(), children, stats }, nodes, edges)
+}
 
-    let subgraph_cnt = children.len();
-    let node_cnt = graph.collect_nodes(id).expect("root should exist in the graph").len();
-    let edge_cnt = graph.collect_edges(id).expect("root should exist in the graph").len();
+fn to_tree(root: &Item) -> TreeItem<'static> {
+    let Stats { subgraphs, nodes, edges, inbound, outbound } = root.stats;
 
-    let id = format!("{} (s: {} n: {} e: {})", id, subgraph_cnt, node_cnt, edge_cnt);
-    let children: Vec<TreeItem<'static>> =
-        children.iter().map(|node| to_tree(node, graph)).collect();
+    let id =
+        format!("{} (s: {subgraphs} n: {nodes} e: {edges} in: {inbound} out: {outbound})", root.id);
+    let children: Vec<TreeItem<'static>> = root.children.iter().map(to_tree).collect();
 
     TreeItem::new(id, children)
 }
@@ -1,38 +1,86 @@
 use crate::viewer::{
     error::{DotViewerError, DotViewerResult},
-    utils::{List, Tree, Trie},
+    modes::SearchMode,
+    utils::{
+        contains, extract_field, truecolor, Derivation, Jumps, List, Op, Origin, Paint, Progress,
+        Scroll, Task, Template, Tree, Trie,
+    },
 };
 
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
 use graphviz_rs::prelude::*;
 
 use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
 use rayon::prelude::*;
 use regex::Regex;
+use tui::layout::Rect;
+
+/// Matches the node id and its item as shown against a key, returning the indices to highlight in the item.
+type Matcher = fn(&str, &str, &str, &Graph) -> Option<Vec<usize>>;
+/// Prev and next nodes linked through each port of a node, by port names.
+pub(crate) type Ports = HashMap<String, (Vec<String>, Vec<String>)>;
+type Adjacency = for<'a> fn(&'a Graph, &NodeId) -> Result<HashSet<&'a NodeId>, DotGraphError>;
+
+/// Maximum width of a title shown in the tab bar.
+const TITLE: usize = 32;
 
-type Matcher = fn(&str, &str, &Graph) -> Option<Vec<usize>>;
+/// Number of characters scrolled horizontally at once.
+const SHIFT: usize = 8;
+
+/// Id of the next view to be constructed.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
 
 /// `View` holds a "view" of the graph that `dot-viewer` is dealing with.
 ///
 /// Named as an analogy to the database concept of "view",
 /// it holds a smaller portion of the original graph.
 pub(crate) struct View {
+    /// Unique id of the view
+    pub id: usize,
     /// Title of the view
     pub title: String,
+    /// View that this view was derived from, if any
+    pub origin: Option<Origin>,
 
-    /// Graph that the view is representing (a portion of the original graph)
-    pub graph: Graph,
+    /// Graph that the view is representing (a portion of the original graph),
+    /// shared with background jobs deriving views from it
+    pub graph: Arc<Graph>,
+    /// Number of edges in the graph
+    pub edges: usize,
 
     /// Current focus
     pub focus: Focus,
     /// Topologically sorted list of all nodes in the view
     pub current: List<String>,
+    /// Index of each node in the topologically sorted list, by node ids
+    pub index: HashMap<String, usize>,
+    /// Ids of all nodes in the order of the list, shared with background jobs without copying
+    ids: Arc<Vec<String>>,
     /// List of previous nodes of the currently selected node
     pub prevs: List<String>,
     /// List of next nodes of the currently selected node
     pub nexts: List<String>,
+    /// Scroll position of the attrs panel of the currently selected node
+    pub attrs: Scroll,
+    /// Area of the minimap beside the nodes list in the last frame
+    pub minimap: Rect,
+
+    /// Template rendering the items of node lists, if shown instead of ids
+    pub template: Option<Template>,
+    /// Items rendered from the template, by node ids
+    pub texts: Arc<HashMap<String, String>>,
+    /// Colors of nodes given in dot, by node ids
+    pub paints: HashMap<String, Paint>,
+    /// Ports of the node whose HTML-like table was drawn last, cached with its id
+    ports: Option<(String, Ports)>,
 
     /// Keyword for match
     pub key: String,
+    /// Search mode that the matches come from
+    pub smode: SearchMode,
     /// List of matching nodes given some input, with highlight index
     pub matches: List<(usize, Vec<usize>)>,
 
@@ -41,6 +89,31 @@ pub(crate) struct View {
 
     /// Tree holding the subgraph tree of the view
     pub subtree: Tree,
+    /// Edges from or to each node, by node ids
+    incident: HashMap<NodeId, Vec<EdgeId>>,
+    /// Innermost subgraph enclosing each node, by node ids
+    clusters: HashMap<NodeId, GraphId>,
+    /// Parent of each subgraph, by subgraph ids
+    parents: HashMap<GraphId, GraphId>,
+
+    /// History of navigations in the view
+    pub jumps: Jumps,
+}
+
+/// `Search` holds the matches of a key in a view, found by a background job.
+pub(crate) struct Search {
+    /// Id of the view searched
+    pub view: usize,
+    pub smode: SearchMode,
+    pub key: String,
+    pub matches: Vec<(usize, Vec<usize>)>,
+}
+
+/// `Heir` carries what a view derived on a background job inherits from its parent.
+struct Heir {
+    parent: usize,
+    template: Option<Template>,
+    derivation: Derivation,
 }
 
 #[derive(PartialEq)]
@@ -48,6 +121,7 @@ pub(crate) enum Focus {
     Current,
     Prev,
     Next,
+    Attrs,
 }
 
 impl View {
@@ -61,45 +135,103 @@ impl View {
 
         let focus = Focus::Current;
         let current = List::from_iter(node_ids);
+        let index = (current.items.iter().enumerate()).map(|(idx, id)| (id.clone(), idx)).collect();
+        let ids = Arc::new(current.items.clone());
         let prevs = List::from_iter(Vec::new());
         let nexts = List::from_iter(Vec::new());
+        let attrs = Scroll::default();
+        let minimap = Rect::default();
+
+        let template = None;
+        let texts = Arc::default();
+
+        let truecolor = truecolor();
+        let paints = (current.items.par_iter())
+            .filter_map(|id| {
+                let node = graph.search_node(id).unwrap();
+                Paint::new(node, truecolor).map(|paint| (id.clone(), paint))
+            })
+            .collect();
+
+        let ports = None;
 
         let key = String::new();
+        let smode = SearchMode::Fuzzy;
         let matches = List::from_iter(Vec::new());
 
-        let subtree = Tree::from_graph(&graph);
-
-        let mut view =
-            Self { title, graph, focus, current, prevs, nexts, key, matches, trie, subtree };
+        let incident = incidences(&graph);
+        let subtree = Tree::from_graph(&graph, &incident);
+        let (clusters, parents) = memberships(&graph);
+
+        let edges = graph.edges().len();
+
+        let jumps = Jumps::default();
+
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let origin = None;
+
+        let graph = Arc::new(graph);
+
+        let mut view = Self {
+            id,
+            title,
+            origin,
+            graph,
+            edges,
+            focus,
+            current,
+            index,
+            ids,
+            prevs,
+            nexts,
+            attrs,
+            minimap,
+            template,
+            texts,
+            paints,
+            ports,
+            key,
+            smode,
+            matches,
+            trie,
+            subtree,
+            incident,
+            clusters,
+            parents,
+            jumps,
+        };
 
         view.update_adjacent().expect("there is always a selected current node on initialization");
 
         Ok(view)
     }
 
-    /// Navigate to the first node in focused list.
-    pub fn goto_first(&mut self) -> DotViewerResult<()> {
+    /// Navigate to the `idx`-th node in focused list, or the last if out of range.
+    pub fn goto_nth(&mut self, idx: usize) -> DotViewerResult<()> {
         match &self.focus {
             Focus::Current => {
-                self.current.first();
-                self.update_adjacent()?
+                let idx = idx.min(self.current.items.len() - 1);
+                let id = self.current.items[idx].clone();
+                self.goto(&id)?
             }
-            Focus::Prev => self.prevs.first(),
-            Focus::Next => self.nexts.first(),
+            Focus::Prev => self.prevs.nth(idx),
+            Focus::Next => self.nexts.nth(idx),
+            Focus::Attrs => self.attrs.nth(idx),
         }
 
         Ok(())
     }
 
-    /// Navigate to the last node in focused list.
-    pub fn goto_last(&mut self) -> DotViewerResult<()> {
+    /// Move the selection in focused list by `offset`, stopping at the first and the last.
+    pub fn scroll(&mut self, offset: isize) -> DotViewerResult<()> {
         match &self.focus {
             Focus::Current => {
-                self.current.last();
+                self.current.scroll(offset);
                 self.update_adjacent()?
             }
-            Focus::Prev => self.prevs.last(),
-            Focus::Next => self.nexts.last(),
+            Focus::Prev => self.prevs.scroll(offset),
+            Focus::Next => self.nexts.scroll(offset),
+            Focus::Attrs => self.attrs.scroll(offset),
         }
 
         Ok(())
@@ -124,9 +256,115 @@ impl View {
             })
     }
 
-    /// Navigate to the currently selected node with `id`.
+    /// Select the matched node, without recording it in the jump list.
+    pub fn select_match(&mut self) -> DotViewerResult<()> {
+        self.matched_id()
+            .map_or(Err(DotViewerError::ViewerError("no node selected".to_string())), |id| {
+                self.select(&id)
+            })
+    }
+
+    /// Navigate along the chain of unique successors (or predecessors if not `forward`),
+    /// until reaching a branch or a merge point, a source or a sink.
+    pub fn goto_chain(&mut self, forward: bool) -> DotViewerResult<()> {
+        let (nexts, prevs) = if forward {
+            (Graph::tos as Adjacency, Graph::froms as Adjacency)
+        } else {
+            (Graph::froms as Adjacency, Graph::tos as Adjacency)
+        };
+
+        let mut id = self.current_id();
+        let mut moved = false;
+        loop {
+            let adjacent = nexts(&self.graph, &id)?;
+            if adjacent.len() != 1 {
+                break;
+            }
+            id = adjacent.into_iter().next().unwrap().clone();
+            moved = true;
+
+            if nexts(&self.graph, &id)?.len() != 1 || prevs(&self.graph, &id)?.len() > 1 {
+                break;
+            }
+        }
+
+        if !moved {
+            let direction = if forward { "successor" } else { "predecessor" };
+            return Err(DotViewerError::ViewerError(format!("no unique {direction} to follow")));
+        }
+
+        self.goto(&id)
+    }
+
+    /// Navigate to the nearest sink (or source if not `forward`) from the current node.
+    pub fn goto_terminal(&mut self, forward: bool) -> DotViewerResult<()> {
+        let nexts = if forward { Graph::tos as Adjacency } else { Graph::froms as Adjacency };
+
+        let start = self.current_id();
+        let mut visited = HashSet::from([start.clone()]);
+        let mut queue = VecDeque::from([start.clone()]);
+
+        while let Some(id) = queue.pop_front() {
+            let mut adjacent: Vec<&String> = nexts(&self.graph, &id)?.into_iter().collect();
+            if adjacent.is_empty() {
+                if id == start {
+                    let terminal = if forward { "sink" } else { "source" };
+                    return Err(DotViewerError::ViewerError(format!("already at a {terminal}")));
+                }
+
+                return self.goto(&id);
+            }
+
+            adjacent.sort_unstable();
+            for next in adjacent {
+                if visited.insert(next.clone()) {
+                    queue.push_back(next.clone());
+                }
+            }
+        }
+
+        unreachable!("a dag always has a reachable terminal node")
+    }
+
+    /// Navigate back in the jump list.
+    pub fn goto_jump_back(&mut self) -> DotViewerResult<()> {
+        let current = self.current_id();
+        let id = (self.jumps.back(&current))
+            .ok_or(DotViewerError::ViewerError("at the oldest jump".to_string()))?;
+
+        self.select(&id)
+    }
+
+    /// Navigate forward in the jump list.
+    pub fn goto_jump_forward(&mut self) -> DotViewerResult<()> {
+        let id = (self.jumps.forward())
+            .ok_or(DotViewerError::ViewerError("at the newest jump".to_string()))?;
+
+        self.select(&id)
+    }
+
+    /// Navigate to the node visited right before the latest jump.
+    pub fn goto_jump_last(&mut self) -> DotViewerResult<()> {
+        let id = (self.jumps.last())
+            .ok_or(DotViewerError::ViewerError("no previous jump".to_string()))?;
+
+        self.goto(&id)
+    }
+
+    /// Navigate to the currently selected node with `id`, recording it in the jump list.
     /// The current node list will be focused on the selected node.
     pub fn goto(&mut self, id: &str) -> DotViewerResult<()> {
+        let from = self.current_id();
+        self.select(id)?;
+
+        self.jumps.record(&from);
+        self.jumps.record(id);
+
+        Ok(())
+    }
+
+    /// Select the node with `id` in the current node list.
+    pub fn select(&mut self, id: &str) -> DotViewerResult<()> {
         let idx = (self.current)
             .find(id.to_string())
             .ok_or(DotViewerError::ViewerError(format!("no such node {id:?}")))?;
@@ -138,50 +376,150 @@ impl View {
     }
 
     /// Apply prefix filter on the view given prefix `key`.
-    /// Returns `Ok` with a new `View` if the prefix yields a valid subgraph.
-    pub fn filter(&mut self) -> DotViewerResult<View> {
-        let node_ids: Vec<&String> =
-            (self.matches.items.iter()).map(|(idx, _)| &self.current.items[*idx]).collect();
-        let graph = self.graph.filter(&node_ids);
-
-        if graph.is_empty() {
-            let key = &self.key;
-            return Err(DotViewerError::ViewerError(format!("no match for keyword {key}")));
-        }
+    /// Returns a task making a new `View` if the prefix yields a valid subgraph.
+    pub fn filter(&self) -> Task<View> {
+        let node_ids: Vec<String> =
+            (self.matches.items.iter()).map(|(idx, _)| self.current.items[*idx].clone()).collect();
+        let graph = self.graph.clone();
+
+        let key = self.key.clone();
+        let title = format!("{} - {key}", self.title);
+        let heir = self.heir(Derivation::Filter { smode: self.smode.clone(), key: key.clone() });
+
+        Box::new(move |progress| {
+            let graph = graph.filter(&node_ids.iter().collect::<Vec<_>>());
+
+            if graph.is_empty() {
+                return Err(DotViewerError::ViewerError(format!("no match for keyword {key}")));
+            }
+
+            heir.view(title, graph, progress)
+        })
+    }
+
+    /// Apply numeric filter on the view, keeping nodes whose `field` extracted by `regex`
+    /// satisfies `op` against `value`.
+    /// Returns a task making a new `View` if any node satisfies the condition.
+    pub fn filter_where(&self, field: &str, regex: &Regex, op: Op, value: f64) -> Task<View> {
+        let node_ids = self.ids.clone();
+        let graph = self.graph.clone();
+        let regex = regex.clone();
+
+        let field = field.to_string();
+        let title = format!("{} - {field}{op}{value}", self.title);
+        let heir =
+            self.heir(Derivation::Where { field: field.clone(), op, value: value.to_string() });
+
+        Box::new(move |progress| {
+            progress.total(node_ids.len());
+
+            let node_ids: Vec<&String> = (node_ids.par_iter())
+                .filter(|id| {
+                    progress.advance();
+                    if progress.cancelled() {
+                        return false;
+                    }
+
+                    let raw = to_raw(id, &graph);
+                    matches!(extract_field(&regex, &raw), Some(lhs) if op.apply(lhs, value))
+                })
+                .collect();
+            progress.check()?;
+
+            let graph = graph.filter(&node_ids);
+
+            if graph.is_empty() {
+                let msg = format!("no node with {field} {op} {value}");
+                return Err(DotViewerError::ViewerError(msg));
+            }
 
-        Self::new(format!("{} - {}", self.title, self.key), graph)
+            heir.view(title, graph, progress)
+        })
     }
 
     /// Extract a subgraph from the view.
-    /// Returns `Ok` with a new `View` if the selected subgraph id is valid.
-    pub fn subgraph(&mut self) -> DotViewerResult<View> {
+    /// Returns a task making a new `View` if the selected subgraph id is valid.
+    pub fn subgraph(&self) -> DotViewerResult<Task<View>> {
         let key = (self.subtree)
             .selected()
             .ok_or(DotViewerError::ViewerError("no subgraph selected".to_string()))?;
 
-        let subgraph =
-            self.graph.subgraph(&key).map_err(|e| DotViewerError::ViewerError(e.to_string()))?;
+        Ok(self.extract(&key))
+    }
 
-        if subgraph.is_empty() {
-            return Err(DotViewerError::ViewerError("empty graph".to_string()));
-        }
+    /// Extract the innermost subgraph enclosing the current node, selecting the node.
+    /// Returns a task making a new `View` if the node is in a subgraph.
+    pub fn cluster(&self) -> DotViewerResult<Task<View>> {
+        let id = self.current_id();
+        let extract = self.extract(&self.cluster_id()?);
+
+        Ok(Box::new(move |progress| {
+            let mut view = extract(progress)?;
+            view.goto(&id)?;
+
+            Ok(view)
+        }))
+    }
+
+    fn extract(&self, key: &str) -> Task<View> {
+        let graph = self.graph.clone();
+
+        let key = key.to_string();
+        let title = format!("{} - {key}", self.title);
+        let heir = self.heir(Derivation::Subgraph { key: key.clone() });
+
+        Box::new(move |progress| {
+            let subgraph =
+                graph.subgraph(&key).map_err(|e| DotViewerError::ViewerError(e.to_string()))?;
 
-        let title = &self.title;
-        Self::new(format!("{title} - {key}"), subgraph)
+            if subgraph.is_empty() {
+                return Err(DotViewerError::ViewerError("empty graph".to_string()));
+            }
+
+            heir.view(title, subgraph, progress)
+        })
     }
 
     /// Get neighbors graph from the selected id in the view.
-    /// Returns `Ok` with a new `View` if the depth is valid.
-    pub fn neighbors(&mut self, depth: usize) -> DotViewerResult<View> {
+    /// Returns a task making a new `View` if the depth is valid.
+    pub fn neighbors(&self, depth: usize) -> Task<View> {
+        let graph = self.graph.clone();
+
         let id = self.current_id();
-        let graph = self.graph.neighbors(&id, depth)?;
+        let title = format!("{} - neighbors-{id}-{depth}", self.title);
+        let heir = self.heir(Derivation::Neighbors { id: id.clone(), depth });
 
-        if graph.is_empty() {
-            return Err(DotViewerError::ViewerError("cannot define a neighbors graph".to_string()));
-        }
+        Box::new(move |progress| {
+            let graph = graph.neighbors(&id, depth)?;
+
+            if graph.is_empty() {
+                let msg = "cannot define a neighbors graph".to_string();
+                return Err(DotViewerError::ViewerError(msg));
+            }
+
+            heir.view(title, graph, progress)
+        })
+    }
+
+    /// Copy the view, selecting the current node.
+    pub fn duplicate(&self) -> Task<View> {
+        let graph = self.graph.clone();
+
+        let id = self.current_id();
+        let title = format!("{} (copy)", self.title);
+        let heir = self.heir(Derivation::Duplicate);
+
+        Box::new(move |progress| {
+            let mut view = heir.view(title, Graph::clone(&graph), progress)?;
+            view.goto(&id)?;
 
-        let title = &self.title;
-        Self::new(format!("{title} - neighbors-{id}-{depth}"), graph)
+            Ok(view)
+        })
+    }
+
+    /// Record a view to be derived from this view by `derivation`.
+    fn heir(&self, derivation: Derivation) -> Heir {
+        Heir { parent: self.id, template: self.template.clone(), derivation }
     }
 
     /// Autocomplete a given keyword, coming from `tab` keybinding.
@@ -203,38 +541,87 @@ impl View {
         let nexts = nexts.iter().map(|n| n.to_string());
         self.nexts = List::from_iter(nexts);
 
-        Ok(())
-    }
-
-    /// Update matches based on the given matching function `match` with input `key`.
-    fn update_matches(&mut self, matcher: Matcher, key: &str) {
-        let matches: Vec<(usize, Vec<usize>)> = (self.current.items.par_iter())
-            .enumerate()
-            .filter_map(|(idx, id)| matcher(id, key, &self.graph).map(|highlight| (idx, highlight)))
-            .collect();
+        self.attrs.nth(0);
 
-        self.key = key.to_string();
-        self.matches = List::from_iter(matches);
+        Ok(())
     }
 
-    /// Update matches in fuzzy search mode.
-    /// Fuzzy matcher matches input against node ids.
-    pub fn update_fuzzy(&mut self, key: &str) {
-        self.update_matches(match_fuzzy, key);
+    /// Search the view for `key` in `smode`.
+    /// Returns a task finding the matches, to be shown by `set_search`.
+    pub fn search(&self, smode: SearchMode, key: &str) -> Task<Search> {
+        let node_ids = self.ids.clone();
+        let texts = self.texts.clone();
+        let graph = self.graph.clone();
+
+        let view = self.id;
+        let key = key.to_string();
+        let matcher: Matcher = match smode {
+            SearchMode::Fuzzy => match_fuzzy,
+            SearchMode::Regex => match_regex,
+        };
+
+        Box::new(move |progress| {
+            let matches = find(&node_ids, &texts, &graph, matcher, &key, progress);
+            progress.check()?;
+
+            Ok(Search { view, smode, key, matches })
+        })
     }
 
-    /// Update matches in regex search mode.
-    /// Regex matcher matches input against node represented in raw dot format string.
-    pub fn update_regex(&mut self, key: &str) {
-        self.update_matches(match_regex, key);
+    /// Show the matches found by a search task.
+    pub fn set_search(&mut self, search: Search) {
+        self.smode = search.smode;
+        self.key = search.key;
+        self.matches = List::from_iter(search.matches);
+        self.update_trie();
     }
 
     /// Update trie based on the current matches.
     pub fn update_trie(&mut self) {
-        let nodes = self.matches.items.iter().map(|(idx, _)| self.current.items[*idx].clone());
+        let nodes = (self.matches.items.iter())
+            .map(|(idx, _)| self.text(&self.current.items[*idx]).to_string());
         self.trie = Trie::from_iter(nodes);
     }
 
+    /// Show items of node lists rendered from `template`, or ids if not given.
+    pub fn set_template(&mut self, template: Option<&Template>) {
+        self.template = template.cloned();
+        self.texts = match template {
+            Some(template) => Arc::new(
+                (self.current.items.par_iter())
+                    .map(|id| {
+                        let node = self.graph.search_node(id).unwrap();
+                        (id.clone(), template.render(node))
+                    })
+                    .collect(),
+            ),
+            None => Arc::default(),
+        };
+
+        let texts = self.current.items.iter().map(|id| self.text(id).to_string());
+        self.trie = Trie::from_iter(texts);
+    }
+
+    /// Item of the node `id` as shown in node lists.
+    pub fn text<'a>(&'a self, id: &'a str) -> &'a str {
+        self.texts.get(id).map_or(id, |text| text.as_str())
+    }
+
+    /// Title elided in the middle to fit in the tab bar, keeping both its root and its latest part.
+    pub fn short_title(&self) -> String {
+        let chars: Vec<char> = self.title.chars().collect();
+        if chars.len() <= TITLE {
+            return self.title.clone();
+        }
+
+        let head = (TITLE - 1) / 2;
+        let tail = TITLE - 1 - head;
+        let head: String = chars[..head].iter().collect();
+        let tail: String = chars[chars.len() - tail..].iter().collect();
+
+        format!("{head}…{tail}")
+    }
+
     pub fn current_id(&self) -> String {
         self.current.selected().expect("there is always a current id selected in a view")
     }
@@ -243,6 +630,181 @@ impl View {
         self.matches.selected().map(|(idx, _)| self.current.items[idx].clone())
     }
 
+    /// Subgraphs enclosing the node `id`, from the root graph to the innermost one.
+    pub fn enclosing(&self, id: &str) -> Vec<&SubGraph> {
+        let mut enclosing = Vec::new();
+
+        let mut innermost = self.clusters.get(id);
+        while let Some(subgraph) = innermost.and_then(|id| self.graph.search_subgraph(id)) {
+            enclosing.push(subgraph);
+            innermost = self.parents.get(subgraph.id());
+        }
+
+        enclosing.reverse();
+        enclosing
+    }
+
+    /// Prev and next nodes linked through each port of the node `id`,
+    /// computed once while the node is previewed, as edges are not indexed by their ends.
+    pub fn ports(&mut self, id: &str) -> &Ports {
+        if !matches!(&self.ports, Some((cached, _)) if cached == id) {
+            self.ports = Some((id.to_string(), self.link_ports(id)));
+        }
+
+        &self.ports.as_ref().expect("ports should be cached").1
+    }
+
+    fn link_ports(&self, id: &str) -> Ports {
+        let mut ports = Ports::new();
+
+        // ports may come with compass points, e.g., `in:n`
+        let name = |port: &String| port.split(':').next().unwrap_or_default().to_string();
+
+        for edge in self.incident(id) {
+            if let (true, Some(port)) = (edge.to() == id, edge.headport()) {
+                ports.entry(name(port)).or_default().0.push(edge.from().clone());
+            }
+            if let (true, Some(port)) = (edge.from() == id, edge.tailport()) {
+                ports.entry(name(port)).or_default().1.push(edge.to().clone());
+            }
+        }
+
+        for (froms, tos) in ports.values_mut() {
+            froms.sort_unstable();
+            tos.sort_unstable();
+        }
+
+        ports
+    }
+
+    /// Id of the innermost subgraph enclosing the current node, other than the root graph.
+    pub fn cluster_id(&self) -> DotViewerResult<String> {
+        let id = self.current_id();
+
+        (self.clusters.get(&id))
+            .filter(|cluster| *cluster != self.graph.id())
+            .cloned()
+            .ok_or(DotViewerError::ViewerError(format!("{id} is not in any subgraph")))
+    }
+
+    /// Path of the subgraphs enclosing the node `id`, e.g., `G > layer3 > attn`.
+    pub fn membership(&self, id: &str) -> String {
+        let enclosing = self.enclosing(id);
+
+        let ids: Vec<String> = enclosing.iter().map(|subgraph| elide(subgraph.id(), 16)).collect();
+        ids.join(" > ")
+    }
+
+    /// Raw dot format string of the node `id`.
+    pub fn raw(&self, id: &str) -> String {
+        to_raw(id, &self.graph)
+    }
+
+    /// Scroll the focused list horizontally by `count` steps, to the right if `right`,
+    /// not beyond the beginning of its longest item.
+    pub fn shift(&mut self, right: bool, count: usize) {
+        let list = match &self.focus {
+            Focus::Current => &self.current,
+            Focus::Prev => &self.prevs,
+            Focus::Next => &self.nexts,
+            Focus::Attrs => return,
+        };
+
+        let longest =
+            (list.items.iter()).map(|id| self.text(id).chars().count()).max().unwrap_or(0);
+        let offset = count.saturating_mul(SHIFT);
+        let shift = if right {
+            list.shift.saturating_add(offset).min(longest.saturating_sub(1))
+        } else {
+            list.shift.saturating_sub(offset)
+        };
+
+        match &self.focus {
+            Focus::Current => self.current.shift = shift,
+            Focus::Prev => self.prevs.shift = shift,
+            Focus::Next => self.nexts.shift = shift,
+            Focus::Attrs => {}
+        }
+    }
+
+    /// Index of the first node of the nodes list at row `y` of the minimap, if it is at column `x`.
+    pub fn minimap_hit(&self, x: u16, y: u16) -> Option<usize> {
+        let area = self.minimap;
+        if !contains(area, x, y) {
+            return None;
+        }
+
+        let (row, height, len) =
+            ((y - area.y) as usize, area.height as usize, self.current.items.len());
+        let idx = if len <= height {
+            row
+        } else {
+            // rows cover items at scale, rounding up to the first item covered
+            row * len / height + usize::from(row * len % height != 0)
+        };
+
+        (idx < len).then_some(idx)
+    }
+
+    /// Node shown in the attrs panel, which is the selected node of the focused prev or next list,
+    /// or the current node otherwise.
+    pub fn previewed_id(&self) -> String {
+        let adjacent = match &self.focus {
+            Focus::Prev => self.prevs.selected(),
+            Focus::Next => self.nexts.selected(),
+            _ => None,
+        };
+
+        adjacent.unwrap_or_else(|| self.current_id())
+    }
+
+    /// Edges from or to the node `id`.
+    pub fn incident(&self, id: &str) -> &[EdgeId] {
+        self.incident.get(id).map_or(&[], |edges| edges.as_slice())
+    }
+
+    /// Edges between the current node and the previewed prev or next node.
+    pub fn previewed_edges(&self) -> Vec<&Edge> {
+        let id = self.current_id();
+
+        let (from, to) = match &self.focus {
+            Focus::Prev => match self.prevs.selected() {
+                Some(prev) => (prev, id),
+                None => return Vec::new(),
+            },
+            Focus::Next => match self.nexts.selected() {
+                Some(next) => (id, next),
+                None => return Vec::new(),
+            },
+            _ => return Vec::new(),
+        };
+
+        (self.incident(&from).iter())
+            .filter(|edge| *edge.from() == from && *edge.to() == to)
+            .filter_map(|edge| self.graph.search_edge(edge))
+            .collect()
+    }
+
+    /// Number of nodes in focused list.
+    pub fn len(&self) -> usize {
+        match &self.focus {
+            Focus::Current => self.current.items.len(),
+            Focus::Prev => self.prevs.items.len(),
+            Focus::Next => self.nexts.items.len(),
+            Focus::Attrs => self.attrs.len,
+        }
+    }
+
+    /// Number of nodes visible in focused list.
+    pub fn height(&self) -> usize {
+        match &self.focus {
+            Focus::Current => self.current.height,
+            Focus::Prev => self.prevs.height,
+            Focus::Next => self.nexts.height,
+            Focus::Attrs => self.attrs.height,
+        }
+    }
+
     pub fn progress_current(&self) -> String {
         let idx = self.current.state.selected().unwrap();
         let len = self.current.items.len();
@@ -251,6 +813,17 @@ impl View {
         format!("[{} / {} ({:.3}%)]", idx + 1, len, percentage)
     }
 
+    /// Breadcrumb of the recent jumps, e.g., `a > b > c`.
+    pub fn breadcrumb(&self) -> String {
+        let recent = self.jumps.recent(4);
+        if recent.len() < 2 {
+            return String::new();
+        }
+
+        let recent: Vec<String> = recent.iter().map(|id| elide(id, 16)).collect();
+        recent.join(" > ")
+    }
+
     pub fn progress_matches(&self) -> String {
         let idx = self.matches.state.selected().unwrap();
         let len = self.matches.items.len();
@@ -260,23 +833,109 @@ impl View {
     }
 }
 
-fn match_fuzzy(id: &str, key: &str, _graph: &Graph) -> Option<Vec<usize>> {
+impl Heir {
+    /// Make the view derived from `graph`, unless the task is cancelled.
+    fn view(self, title: String, graph: Graph, progress: &Progress) -> DotViewerResult<View> {
+        progress.check()?;
+
+        let mut view = View::new(title, graph)?;
+        view.origin = Some(Origin { parent: self.parent, derivation: self.derivation });
+        view.set_template(self.template.as_ref());
+
+        Ok(view)
+    }
+}
+
+/// Edges from or to each node in `graph`.
+fn incidences(graph: &Graph) -> HashMap<NodeId, Vec<EdgeId>> {
+    let mut incident: HashMap<NodeId, Vec<EdgeId>> = HashMap::new();
+
+    for edge in graph.edges() {
+        incident.entry(edge.from().clone()).or_default().push(edge.clone());
+        if edge.to() != edge.from() {
+            incident.entry(edge.to().clone()).or_default().push(edge.clone());
+        }
+    }
+
+    incident
+}
+
+/// Innermost subgraph enclosing each node, and parent of each subgraph in `graph`.
+fn memberships(graph: &Graph) -> (HashMap<NodeId, GraphId>, HashMap<GraphId, GraphId>) {
+    let mut clusters = HashMap::new();
+    let mut parents = HashMap::new();
+
+    for subgraph in graph.subgraphs().into_iter().filter_map(|id| graph.search_subgraph(id)) {
+        for node in subgraph.nodes() {
+            clusters.insert(node.clone(), subgraph.id().clone());
+        }
+        for child in subgraph.subgraphs() {
+            parents.insert(child.clone(), subgraph.id().clone());
+        }
+    }
+
+    (clusters, parents)
+}
+
+fn elide(id: &str, width: usize) -> String {
+    if id.chars().count() <= width {
+        id.to_string()
+    } else {
+        let id: String = id.chars().take(width - 1).collect();
+        format!("{id}…")
+    }
+}
+
+/// Match the nodes `node_ids` shown as `texts` against `key`, returning their indices
+/// with the indices to highlight in their items.
+fn find(
+    node_ids: &[String],
+    texts: &HashMap<String, String>,
+    graph: &Graph,
+    matcher: Matcher,
+    key: &str,
+    progress: &Progress,
+) -> Vec<(usize, Vec<usize>)> {
+    progress.total(node_ids.len());
+
+    (node_ids.par_iter())
+        .enumerate()
+        .filter_map(|(idx, id)| {
+            if progress.cancelled() {
+                return None;
+            }
+            progress.advance();
+
+            let text = texts.get(id).map_or(id.as_str(), |text| text.as_str());
+            let highlight = matcher(id, text, key, graph);
+            highlight.map(|highlight| (idx, highlight))
+        })
+        .collect()
+}
+
+fn match_fuzzy(_id: &str, text: &str, key: &str, _graph: &Graph) -> Option<Vec<usize>> {
     let matcher = SkimMatcherV2::default();
 
-    matcher.fuzzy_indices(id, key).map(|(_, idxs)| idxs)
+    matcher.fuzzy_indices(text, key).map(|(_, idxs)| idxs)
 }
 
-fn match_regex(id: &str, key: &str, graph: &Graph) -> Option<Vec<usize>> {
+fn match_regex(id: &str, text: &str, key: &str, graph: &Graph) -> Option<Vec<usize>> {
     if let Ok(matcher) = Regex::new(key) {
-        let node = graph.search_node(&id.to_string()).unwrap();
+        let raw = to_raw(id, graph);
 
-        let mut buffer = Vec::new();
-        node.to_dot(0, &mut buffer).expect("to_dot should succeed");
-        let raw = std::str::from_utf8(&buffer).unwrap();
-
-        let highlight: Vec<usize> = (0..id.len()).collect();
-        matcher.is_match(raw).then_some(highlight)
+        let highlight: Vec<usize> = (0..text.chars().count()).collect();
+        matcher.is_match(&raw).then_some(highlight)
     } else {
         None
     }
 }
+
+/// Represent the node `id` in raw dot format string.
+fn to_raw(id: &str, graph: &Graph) -> String {
+    let node = graph.search_node(&id.to_string()).unwrap();
+
+    let mut buffer = Vec::new();
+    node.to_dot(0, &mut buffer).expect("to_dot should succeed");
+
+    String::from_utf8(buffer).unwrap()
+}